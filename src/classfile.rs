@@ -2,13 +2,15 @@ use bitflags::bitflags;
 use nom::{
     bytes::complete::take,
     combinator::map,
-    multi::{count, many1},
+    multi::count,
     number::complete::{be_u16, be_u32},
-    IResult,
 };
 
+use crate::error::ClassFileError;
 use crate::instruction::Instruction;
 
+type IResult<'a, T> = nom::IResult<&'a [u8], T, ClassFileError>;
+
 #[derive(Debug)]
 #[repr(u8)]
 enum CpInfo {
@@ -62,10 +64,48 @@ enum CpInfo {
         bootstrap_method_attr_index: u16,
         name_and_type_index: u16,
     } = 18,
+    Dynamic {
+        bootstrap_method_attr_index: u16,
+        name_and_type_index: u16,
+    } = 17,
+    Module {
+        name_index: u16,
+    } = 19,
+    Package {
+        name_index: u16,
+    } = 20,
+    /// Not a real constant-pool tag. `Long` and `Double` entries take up two
+    /// consecutive indices, so the index following one of them is unusable
+    /// and holds this placeholder instead of a parsed entry.
+    Unusable = 0,
 }
 
 impl CpInfo {
-    fn read(input: &[u8]) -> IResult<&[u8], CpInfo> {
+    /// The tag name used in [`ClassFileError::ConstantPoolTypeMismatch`] messages.
+    fn tag_name(&self) -> &'static str {
+        match self {
+            CpInfo::Class { .. } => "Class",
+            CpInfo::FieldRef { .. } => "FieldRef",
+            CpInfo::MethodRef { .. } => "MethodRef",
+            CpInfo::InterfaceMethodRef { .. } => "InterfaceMethodRef",
+            CpInfo::String { .. } => "String",
+            CpInfo::Integer { .. } => "Integer",
+            CpInfo::Float { .. } => "Float",
+            CpInfo::Long { .. } => "Long",
+            CpInfo::Double { .. } => "Double",
+            CpInfo::NameAndType { .. } => "NameAndType",
+            CpInfo::Utf8 { .. } => "Utf8",
+            CpInfo::MethodHandle { .. } => "MethodHandle",
+            CpInfo::MethodType { .. } => "MethodType",
+            CpInfo::InvokeDynamic { .. } => "InvokeDynamic",
+            CpInfo::Dynamic { .. } => "Dynamic",
+            CpInfo::Module { .. } => "Module",
+            CpInfo::Package { .. } => "Package",
+            CpInfo::Unusable => "Unusable",
+        }
+    }
+
+    fn read(input: &[u8]) -> IResult<'_, CpInfo> {
         let (input, tag) = nom::number::complete::u8(input)?;
         match tag {
             7 => {
@@ -154,8 +194,15 @@ impl CpInfo {
                 let (input, length) = be_u16(input)?;
                 let (input, bytes) = take(length as usize)(input)?;
 
-                let str = cesu8::from_java_cesu8(bytes).map_err(|err| {
-                    nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+                // `cesu8::from_java_cesu8` implements the JVM's modified UTF-8 exactly:
+                // NUL as the overlong two-byte `0xC0 0x80`, and supplementary characters
+                // as a six-byte CESU-8 surrogate pair rather than a four-byte UTF-8
+                // sequence. It's fallible rather than panicking on malformed input, so
+                // a truncated or adversarial class file surfaces as a parse error here.
+                let str = cesu8::from_java_cesu8(bytes).map_err(|_| {
+                    nom::Err::Failure(ClassFileError::InvalidModifiedUtf8 {
+                        bytes: bytes.to_vec(),
+                    })
                 })?;
 
                 Ok((
@@ -191,8 +238,295 @@ impl CpInfo {
                     },
                 ))
             }
-            _ => unreachable!(),
+            17 => {
+                let (input, bootstrap_method_attr_index) = be_u16(input)?;
+                let (input, name_and_type_index) = be_u16(input)?;
+                Ok((
+                    input,
+                    CpInfo::Dynamic {
+                        bootstrap_method_attr_index,
+                        name_and_type_index,
+                    },
+                ))
+            }
+            19 => {
+                let (input, name_index) = be_u16(input)?;
+                Ok((input, CpInfo::Module { name_index }))
+            }
+            20 => {
+                let (input, name_index) = be_u16(input)?;
+                Ok((input, CpInfo::Package { name_index }))
+            }
+            _ => Err(nom::Err::Failure(ClassFileError::UnknownConstantTag(tag))),
+        }
+    }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            CpInfo::Class { name_index } => {
+                out.push(7);
+                out.extend(name_index.to_be_bytes());
+            }
+            CpInfo::FieldRef {
+                class_index,
+                name_and_type_index,
+            } => {
+                out.push(9);
+                out.extend(class_index.to_be_bytes());
+                out.extend(name_and_type_index.to_be_bytes());
+            }
+            CpInfo::MethodRef {
+                class_index,
+                name_and_type_index,
+            } => {
+                out.push(10);
+                out.extend(class_index.to_be_bytes());
+                out.extend(name_and_type_index.to_be_bytes());
+            }
+            CpInfo::InterfaceMethodRef {
+                class_index,
+                name_and_type_index,
+            } => {
+                out.push(11);
+                out.extend(class_index.to_be_bytes());
+                out.extend(name_and_type_index.to_be_bytes());
+            }
+            CpInfo::String { string_index } => {
+                out.push(8);
+                out.extend(string_index.to_be_bytes());
+            }
+            CpInfo::Integer { bytes } => {
+                out.push(3);
+                out.extend(bytes.to_be_bytes());
+            }
+            CpInfo::Float { bytes } => {
+                out.push(4);
+                out.extend(bytes.to_be_bytes());
+            }
+            CpInfo::Long {
+                high_bytes,
+                low_bytes,
+            } => {
+                out.push(5);
+                out.extend(high_bytes.to_be_bytes());
+                out.extend(low_bytes.to_be_bytes());
+            }
+            CpInfo::Double {
+                high_bytes,
+                low_bytes,
+            } => {
+                out.push(6);
+                out.extend(high_bytes.to_be_bytes());
+                out.extend(low_bytes.to_be_bytes());
+            }
+            CpInfo::NameAndType {
+                name_index,
+                descriptor_index,
+            } => {
+                out.push(12);
+                out.extend(name_index.to_be_bytes());
+                out.extend(descriptor_index.to_be_bytes());
+            }
+            CpInfo::Utf8 { value } => {
+                let bytes = cesu8::to_java_cesu8(value);
+                out.push(1);
+                out.extend((bytes.len() as u16).to_be_bytes());
+                out.extend(bytes.iter());
+            }
+            CpInfo::MethodHandle {
+                reference_kind,
+                reference_index,
+            } => {
+                out.push(15);
+                out.push(*reference_kind);
+                out.extend(reference_index.to_be_bytes());
+            }
+            CpInfo::MethodType { descriptor_index } => {
+                out.push(16);
+                out.extend(descriptor_index.to_be_bytes());
+            }
+            CpInfo::InvokeDynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            } => {
+                out.push(18);
+                out.extend(bootstrap_method_attr_index.to_be_bytes());
+                out.extend(name_and_type_index.to_be_bytes());
+            }
+            CpInfo::Dynamic {
+                bootstrap_method_attr_index,
+                name_and_type_index,
+            } => {
+                out.push(17);
+                out.extend(bootstrap_method_attr_index.to_be_bytes());
+                out.extend(name_and_type_index.to_be_bytes());
+            }
+            CpInfo::Module { name_index } => {
+                out.push(19);
+                out.extend(name_index.to_be_bytes());
+            }
+            CpInfo::Package { name_index } => {
+                out.push(20);
+                out.extend(name_index.to_be_bytes());
+            }
+            CpInfo::Unusable => {}
         }
+        out
+    }
+}
+
+/// A borrowed view over a [`ClassFile`]'s constant pool that resolves symbolic
+/// references (`Class`, `NameAndType`, `MethodRef`, ...) without making callers
+/// index into the raw table and match on [`CpInfo`] themselves.
+#[derive(Debug, Clone, Copy)]
+pub struct ConstantPool<'a> {
+    entries: &'a [CpInfo],
+}
+
+impl<'a> ConstantPool<'a> {
+    fn new(entries: &'a [CpInfo]) -> Self {
+        Self { entries }
+    }
+
+    fn get(&self, index: u16) -> Result<&'a CpInfo, ClassFileError> {
+        self.entries
+            .get(index as usize)
+            .ok_or(ClassFileError::ConstantPoolIndexOutOfRange(index))
+    }
+
+    fn expect<T>(
+        &self,
+        index: u16,
+        expected: &'static str,
+        entry: &'a CpInfo,
+        matched: Option<T>,
+    ) -> Result<T, ClassFileError> {
+        matched.ok_or_else(|| ClassFileError::ConstantPoolTypeMismatch {
+            index,
+            expected,
+            found: entry.tag_name(),
+        })
+    }
+
+    pub fn utf8(&self, index: u16) -> Result<&'a str, ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::Utf8 { value } => Some(value.as_ref()),
+            _ => None,
+        };
+        self.expect(index, "Utf8", entry, matched)
+    }
+
+    /// Resolves a `Class` entry's `name_index` to its `Utf8` binary name.
+    pub fn class_name(&self, index: u16) -> Result<&'a str, ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::Class { name_index } => Some(*name_index),
+            _ => None,
+        };
+        self.utf8(self.expect(index, "Class", entry, matched)?)
+    }
+
+    pub fn name_and_type(&self, index: u16) -> Result<(&'a str, &'a str), ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::NameAndType {
+                name_index,
+                descriptor_index,
+            } => Some((*name_index, *descriptor_index)),
+            _ => None,
+        };
+        let (name_index, descriptor_index) = self.expect(index, "NameAndType", entry, matched)?;
+        Ok((self.utf8(name_index)?, self.utf8(descriptor_index)?))
+    }
+
+    /// Resolves a `MethodRef` entry to `(class_name, method_name, descriptor)`.
+    pub fn method_ref(&self, index: u16) -> Result<(&'a str, &'a str, &'a str), ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::MethodRef {
+                class_index,
+                name_and_type_index,
+            } => Some((*class_index, *name_and_type_index)),
+            _ => None,
+        };
+        let (class_index, name_and_type_index) = self.expect(index, "MethodRef", entry, matched)?;
+        let class_name = self.class_name(class_index)?;
+        let (method_name, descriptor) = self.name_and_type(name_and_type_index)?;
+        Ok((class_name, method_name, descriptor))
+    }
+
+    /// Resolves a `FieldRef` entry to `(class_name, field_name, descriptor)`.
+    pub fn field_ref(&self, index: u16) -> Result<(&'a str, &'a str, &'a str), ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::FieldRef {
+                class_index,
+                name_and_type_index,
+            } => Some((*class_index, *name_and_type_index)),
+            _ => None,
+        };
+        let (class_index, name_and_type_index) = self.expect(index, "FieldRef", entry, matched)?;
+        let class_name = self.class_name(class_index)?;
+        let (field_name, descriptor) = self.name_and_type(name_and_type_index)?;
+        Ok((class_name, field_name, descriptor))
+    }
+
+    /// Resolves an `Integer` entry to its signed value.
+    pub fn integer(&self, index: u16) -> Result<i32, ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::Integer { bytes } => Some(*bytes as i32),
+            _ => None,
+        };
+        self.expect(index, "Integer", entry, matched)
+    }
+
+    /// Resolves a `Float` entry to its value.
+    pub fn float(&self, index: u16) -> Result<f32, ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::Float { bytes } => Some(f32::from_bits(*bytes)),
+            _ => None,
+        };
+        self.expect(index, "Float", entry, matched)
+    }
+
+    /// Resolves a `Long` entry to its signed value.
+    pub fn long(&self, index: u16) -> Result<i64, ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::Long {
+                high_bytes,
+                low_bytes,
+            } => Some((((*high_bytes as u64) << 32) | *low_bytes as u64) as i64),
+            _ => None,
+        };
+        self.expect(index, "Long", entry, matched)
+    }
+
+    /// Resolves a `Double` entry to its value.
+    pub fn double(&self, index: u16) -> Result<f64, ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::Double {
+                high_bytes,
+                low_bytes,
+            } => Some(f64::from_bits(((*high_bytes as u64) << 32) | *low_bytes as u64)),
+            _ => None,
+        };
+        self.expect(index, "Double", entry, matched)
+    }
+
+    /// Resolves a `String` entry's `string_index` to its `Utf8` value.
+    pub fn string(&self, index: u16) -> Result<&'a str, ClassFileError> {
+        let entry = self.get(index)?;
+        let matched = match entry {
+            CpInfo::String { string_index } => Some(*string_index),
+            _ => None,
+        };
+        self.utf8(self.expect(index, "String", entry, matched)?)
     }
 }
 
@@ -212,7 +546,7 @@ bitflags! {
 }
 
 impl FieldAccessFlags {
-    fn read(input: &[u8]) -> IResult<&[u8], Self> {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
         map(be_u16, Self::from_bits_retain)(input)
     }
 }
@@ -226,7 +560,7 @@ struct ExceptionTableEntry {
 }
 
 impl ExceptionTableEntry {
-    fn read(input: &[u8]) -> IResult<&[u8], Self> {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
         let (input, start_pc) = be_u16(input)?;
         let (input, end_pc) = be_u16(input)?;
         let (input, handler_pc) = be_u16(input)?;
@@ -241,6 +575,15 @@ impl ExceptionTableEntry {
             },
         ))
     }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.start_pc.to_be_bytes());
+        out.extend(self.end_pc.to_be_bytes());
+        out.extend(self.handler_pc.to_be_bytes());
+        out.extend(self.catch_type.to_be_bytes());
+        out
+    }
 }
 
 #[derive(Debug)]
@@ -250,7 +593,7 @@ struct LineNumberTableEntry {
 }
 
 impl LineNumberTableEntry {
-    fn read(input: &[u8]) -> IResult<&[u8], Self> {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
         let (input, start_pc) = be_u16(input)?;
         let (input, line_number) = be_u16(input)?;
         Ok((
@@ -261,6 +604,335 @@ impl LineNumberTableEntry {
             },
         ))
     }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.start_pc.to_be_bytes());
+        out.extend(self.line_number.to_be_bytes());
+        out
+    }
+}
+
+bitflags! {
+    #[derive(Debug)]
+    pub struct InnerClassAccessFlags: u16 {
+        const PUBLIC = 0x0001;
+        const PRIVATE = 0x0002;
+        const PROTECTED = 0x0004;
+        const STATIC = 0x0008;
+        const FINAL = 0x0010;
+        const INTERFACE = 0x0200;
+        const ABSTRACT = 0x0400;
+        const SYNTHETIC = 0x1000;
+        const ANNOTATION = 0x2000;
+        const ENUM = 0x4000;
+    }
+}
+
+impl InnerClassAccessFlags {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
+        map(be_u16, Self::from_bits_retain)(input)
+    }
+}
+
+#[derive(Debug)]
+struct InnerClassEntry {
+    inner_class_info_index: u16,
+    outer_class_info_index: u16,
+    inner_name_index: u16,
+    inner_class_access_flags: InnerClassAccessFlags,
+}
+
+impl InnerClassEntry {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
+        let (input, inner_class_info_index) = be_u16(input)?;
+        let (input, outer_class_info_index) = be_u16(input)?;
+        let (input, inner_name_index) = be_u16(input)?;
+        let (input, inner_class_access_flags) = InnerClassAccessFlags::read(input)?;
+        Ok((
+            input,
+            Self {
+                inner_class_info_index,
+                outer_class_info_index,
+                inner_name_index,
+                inner_class_access_flags,
+            },
+        ))
+    }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.inner_class_info_index.to_be_bytes());
+        out.extend(self.outer_class_info_index.to_be_bytes());
+        out.extend(self.inner_name_index.to_be_bytes());
+        out.extend(self.inner_class_access_flags.bits().to_be_bytes());
+        out
+    }
+}
+
+#[derive(Debug)]
+struct BootstrapMethod {
+    bootstrap_method_ref: u16,
+    bootstrap_arguments: Box<[u16]>,
+}
+
+impl BootstrapMethod {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
+        let (input, bootstrap_method_ref) = be_u16(input)?;
+        let (input, num_bootstrap_arguments) = be_u16(input)?;
+        let (input, bootstrap_arguments) =
+            count(be_u16, num_bootstrap_arguments as usize)(input)?;
+        Ok((
+            input,
+            Self {
+                bootstrap_method_ref,
+                bootstrap_arguments: bootstrap_arguments.into_boxed_slice(),
+            },
+        ))
+    }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.bootstrap_method_ref.to_be_bytes());
+        out.extend((self.bootstrap_arguments.len() as u16).to_be_bytes());
+        for argument in self.bootstrap_arguments.iter() {
+            out.extend(argument.to_be_bytes());
+        }
+        out
+    }
+}
+
+#[derive(Debug)]
+enum VerificationTypeInfo {
+    Top,
+    Integer,
+    Float,
+    Double,
+    Long,
+    Null,
+    UninitializedThis,
+    Object { cpool_index: u16 },
+    Uninitialized { offset: u16 },
+}
+
+impl VerificationTypeInfo {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
+        let (input, tag) = nom::number::complete::u8(input)?;
+        match tag {
+            0 => Ok((input, Self::Top)),
+            1 => Ok((input, Self::Integer)),
+            2 => Ok((input, Self::Float)),
+            3 => Ok((input, Self::Double)),
+            4 => Ok((input, Self::Long)),
+            5 => Ok((input, Self::Null)),
+            6 => Ok((input, Self::UninitializedThis)),
+            7 => {
+                let (input, cpool_index) = be_u16(input)?;
+                Ok((input, Self::Object { cpool_index }))
+            }
+            8 => {
+                let (input, offset) = be_u16(input)?;
+                Ok((input, Self::Uninitialized { offset }))
+            }
+            _ => Err(nom::Err::Failure(
+                ClassFileError::UnknownVerificationTypeTag(tag),
+            )),
+        }
+    }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            Self::Top => out.push(0),
+            Self::Integer => out.push(1),
+            Self::Float => out.push(2),
+            Self::Double => out.push(3),
+            Self::Long => out.push(4),
+            Self::Null => out.push(5),
+            Self::UninitializedThis => out.push(6),
+            Self::Object { cpool_index } => {
+                out.push(7);
+                out.extend(cpool_index.to_be_bytes());
+            }
+            Self::Uninitialized { offset } => {
+                out.push(8);
+                out.extend(offset.to_be_bytes());
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug)]
+enum StackMapFrame {
+    SameFrame {
+        offset_delta: u8,
+    },
+    SameLocals1StackItemFrame {
+        offset_delta: u8,
+        stack: VerificationTypeInfo,
+    },
+    SameLocals1StackItemFrameExtended {
+        offset_delta: u16,
+        stack: VerificationTypeInfo,
+    },
+    ChopFrame {
+        offset_delta: u16,
+        absent_locals: u8,
+    },
+    SameFrameExtended {
+        offset_delta: u16,
+    },
+    AppendFrame {
+        offset_delta: u16,
+        locals: Box<[VerificationTypeInfo]>,
+    },
+    FullFrame {
+        offset_delta: u16,
+        locals: Box<[VerificationTypeInfo]>,
+        stack: Box<[VerificationTypeInfo]>,
+    },
+}
+
+impl StackMapFrame {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
+        let (input, frame_type) = nom::number::complete::u8(input)?;
+        match frame_type {
+            0..=63 => Ok((
+                input,
+                Self::SameFrame {
+                    offset_delta: frame_type,
+                },
+            )),
+            64..=127 => {
+                let (input, stack) = VerificationTypeInfo::read(input)?;
+                Ok((
+                    input,
+                    Self::SameLocals1StackItemFrame {
+                        offset_delta: frame_type - 64,
+                        stack,
+                    },
+                ))
+            }
+            247 => {
+                let (input, offset_delta) = be_u16(input)?;
+                let (input, stack) = VerificationTypeInfo::read(input)?;
+                Ok((
+                    input,
+                    Self::SameLocals1StackItemFrameExtended {
+                        offset_delta,
+                        stack,
+                    },
+                ))
+            }
+            248..=250 => {
+                let (input, offset_delta) = be_u16(input)?;
+                Ok((
+                    input,
+                    Self::ChopFrame {
+                        offset_delta,
+                        absent_locals: 251 - frame_type,
+                    },
+                ))
+            }
+            251 => {
+                let (input, offset_delta) = be_u16(input)?;
+                Ok((input, Self::SameFrameExtended { offset_delta }))
+            }
+            252..=254 => {
+                let (input, offset_delta) = be_u16(input)?;
+                let (input, locals) =
+                    count(VerificationTypeInfo::read, (frame_type - 251) as usize)(input)?;
+                Ok((
+                    input,
+                    Self::AppendFrame {
+                        offset_delta,
+                        locals: locals.into_boxed_slice(),
+                    },
+                ))
+            }
+            255 => {
+                let (input, offset_delta) = be_u16(input)?;
+                let (input, number_of_locals) = be_u16(input)?;
+                let (input, locals) =
+                    count(VerificationTypeInfo::read, number_of_locals as usize)(input)?;
+                let (input, number_of_stack_items) = be_u16(input)?;
+                let (input, stack) =
+                    count(VerificationTypeInfo::read, number_of_stack_items as usize)(input)?;
+                Ok((
+                    input,
+                    Self::FullFrame {
+                        offset_delta,
+                        locals: locals.into_boxed_slice(),
+                        stack: stack.into_boxed_slice(),
+                    },
+                ))
+            }
+            _ => Err(nom::Err::Failure(ClassFileError::ReservedStackMapFrameType(
+                frame_type,
+            ))),
+        }
+    }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            Self::SameFrame { offset_delta } => out.push(*offset_delta),
+            Self::SameLocals1StackItemFrame {
+                offset_delta,
+                stack,
+            } => {
+                out.push(offset_delta + 64);
+                out.extend(stack.write());
+            }
+            Self::SameLocals1StackItemFrameExtended {
+                offset_delta,
+                stack,
+            } => {
+                out.push(247);
+                out.extend(offset_delta.to_be_bytes());
+                out.extend(stack.write());
+            }
+            Self::ChopFrame {
+                offset_delta,
+                absent_locals,
+            } => {
+                out.push(251 - absent_locals);
+                out.extend(offset_delta.to_be_bytes());
+            }
+            Self::SameFrameExtended { offset_delta } => {
+                out.push(251);
+                out.extend(offset_delta.to_be_bytes());
+            }
+            Self::AppendFrame {
+                offset_delta,
+                locals,
+            } => {
+                out.push(251 + locals.len() as u8);
+                out.extend(offset_delta.to_be_bytes());
+                for local in locals.iter() {
+                    out.extend(local.write());
+                }
+            }
+            Self::FullFrame {
+                offset_delta,
+                locals,
+                stack,
+            } => {
+                out.push(255);
+                out.extend(offset_delta.to_be_bytes());
+                out.extend((locals.len() as u16).to_be_bytes());
+                for local in locals.iter() {
+                    out.extend(local.write());
+                }
+                out.extend((stack.len() as u16).to_be_bytes());
+                for item in stack.iter() {
+                    out.extend(item.write());
+                }
+            }
+        }
+        out
+    }
 }
 
 #[derive(Debug)]
@@ -268,7 +940,6 @@ enum Attribute {
     Code {
         max_stack: u16,
         max_locals: u16,
-        code_length: u32, // TODO: byte length. required?
         code: Box<[Instruction]>,
         exception_table: Box<[ExceptionTableEntry]>,
         attributes: Box<[AttributeInfo]>,
@@ -279,11 +950,106 @@ enum Attribute {
     SourceFile {
         source_file_index: u16,
     },
+    ConstantValue {
+        constantvalue_index: u16,
+    },
+    Exceptions {
+        exception_index_table: Box<[u16]>,
+    },
+    InnerClasses {
+        classes: Box<[InnerClassEntry]>,
+    },
+    BootstrapMethods {
+        bootstrap_methods: Box<[BootstrapMethod]>,
+    },
+    StackMapTable {
+        entries: Box<[StackMapFrame]>,
+    },
     Unknown {
         bytes: Vec<u8>,
     },
 }
 
+impl Attribute {
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            Attribute::Code {
+                max_stack,
+                max_locals,
+                code,
+                exception_table,
+                attributes,
+                ..
+            } => {
+                out.extend(max_stack.to_be_bytes());
+                out.extend(max_locals.to_be_bytes());
+
+                let mut code_bytes = Vec::new();
+                for instruction in code.iter() {
+                    code_bytes.extend(instruction.write(code_bytes.len()));
+                }
+                out.extend((code_bytes.len() as u32).to_be_bytes());
+                out.extend(code_bytes);
+
+                out.extend((exception_table.len() as u16).to_be_bytes());
+                for entry in exception_table.iter() {
+                    out.extend(entry.write());
+                }
+
+                out.extend((attributes.len() as u16).to_be_bytes());
+                for attribute in attributes.iter() {
+                    out.extend(attribute.write());
+                }
+            }
+            Attribute::LineNumberTable { line_number_table } => {
+                out.extend((line_number_table.len() as u16).to_be_bytes());
+                for entry in line_number_table.iter() {
+                    out.extend(entry.write());
+                }
+            }
+            Attribute::SourceFile { source_file_index } => {
+                out.extend(source_file_index.to_be_bytes());
+            }
+            Attribute::ConstantValue {
+                constantvalue_index,
+            } => {
+                out.extend(constantvalue_index.to_be_bytes());
+            }
+            Attribute::Exceptions {
+                exception_index_table,
+            } => {
+                out.extend((exception_index_table.len() as u16).to_be_bytes());
+                for index in exception_index_table.iter() {
+                    out.extend(index.to_be_bytes());
+                }
+            }
+            Attribute::InnerClasses { classes } => {
+                out.extend((classes.len() as u16).to_be_bytes());
+                for entry in classes.iter() {
+                    out.extend(entry.write());
+                }
+            }
+            Attribute::BootstrapMethods { bootstrap_methods } => {
+                out.extend((bootstrap_methods.len() as u16).to_be_bytes());
+                for method in bootstrap_methods.iter() {
+                    out.extend(method.write());
+                }
+            }
+            Attribute::StackMapTable { entries } => {
+                out.extend((entries.len() as u16).to_be_bytes());
+                for entry in entries.iter() {
+                    out.extend(entry.write());
+                }
+            }
+            Attribute::Unknown { bytes } => {
+                out.extend(bytes);
+            }
+        }
+        out
+    }
+}
+
 #[derive(Debug)]
 struct AttributeInfo {
     attribute_name_index: u16,
@@ -291,28 +1057,30 @@ struct AttributeInfo {
 }
 
 impl AttributeInfo {
-    fn read<'a>(input: &'a [u8], constant_pool: &[CpInfo]) -> IResult<&'a [u8], Self> {
+    fn read<'a>(input: &'a [u8], constant_pool: ConstantPool<'_>) -> IResult<'a, Self> {
         let (input, attribute_name_index) = be_u16(input)?;
         let (input, attribute_length) = be_u32(input)?;
 
-        let attribute_name = match &constant_pool[attribute_name_index as usize] {
-            CpInfo::Utf8 { value, .. } => value,
-            _ => {
-                return Err(nom::Err::Error(nom::error::Error {
-                    input,
-                    code: nom::error::ErrorKind::Fail,
-                }))
-            }
-        };
+        let attribute_name = constant_pool
+            .utf8(attribute_name_index)
+            .map_err(nom::Err::Failure)?;
 
-        let (input, attribute) = match attribute_name.as_ref() {
+        let (input, attribute) = match attribute_name {
             "Code" => {
                 let (input, max_stack) = be_u16(input)?;
                 let (input, max_locals) = be_u16(input)?;
                 let (input, code_length) = be_u32(input)?;
                 let (input, code) = take(code_length as usize)(input)?;
 
-                let (_, instructions) = many1(Instruction::read)(code)?;
+                let mut instructions = Vec::new();
+                let mut remaining = code;
+                let mut pc = 0usize;
+                while !remaining.is_empty() {
+                    let (rest, instruction) = Instruction::read(remaining, pc)?;
+                    pc += remaining.len() - rest.len();
+                    remaining = rest;
+                    instructions.push(instruction);
+                }
 
                 let (input, exception_table_length) = be_u16(input)?;
                 let (input, exception_table) =
@@ -328,7 +1096,6 @@ impl AttributeInfo {
                     Attribute::Code {
                         max_stack,
                         max_locals,
-                        code_length,
                         code: instructions.into_boxed_slice(),
                         exception_table: exception_table.into_boxed_slice(),
                         attributes: attributes.into_boxed_slice(),
@@ -352,6 +1119,59 @@ impl AttributeInfo {
                 let (input, source_file_index) = be_u16(input)?;
                 (input, Attribute::SourceFile { source_file_index })
             }
+            "ConstantValue" => {
+                let (input, constantvalue_index) = be_u16(input)?;
+                (
+                    input,
+                    Attribute::ConstantValue {
+                        constantvalue_index,
+                    },
+                )
+            }
+            "Exceptions" => {
+                let (input, number_of_exceptions) = be_u16(input)?;
+                let (input, exception_index_table) =
+                    count(be_u16, number_of_exceptions as usize)(input)?;
+                (
+                    input,
+                    Attribute::Exceptions {
+                        exception_index_table: exception_index_table.into_boxed_slice(),
+                    },
+                )
+            }
+            "InnerClasses" => {
+                let (input, number_of_classes) = be_u16(input)?;
+                let (input, classes) =
+                    count(InnerClassEntry::read, number_of_classes as usize)(input)?;
+                (
+                    input,
+                    Attribute::InnerClasses {
+                        classes: classes.into_boxed_slice(),
+                    },
+                )
+            }
+            "BootstrapMethods" => {
+                let (input, num_bootstrap_methods) = be_u16(input)?;
+                let (input, bootstrap_methods) =
+                    count(BootstrapMethod::read, num_bootstrap_methods as usize)(input)?;
+                (
+                    input,
+                    Attribute::BootstrapMethods {
+                        bootstrap_methods: bootstrap_methods.into_boxed_slice(),
+                    },
+                )
+            }
+            "StackMapTable" => {
+                let (input, number_of_entries) = be_u16(input)?;
+                let (input, entries) =
+                    count(StackMapFrame::read, number_of_entries as usize)(input)?;
+                (
+                    input,
+                    Attribute::StackMapTable {
+                        entries: entries.into_boxed_slice(),
+                    },
+                )
+            }
             _ => {
                 let (input, bytes) = take(attribute_length as usize)(input)?;
                 (
@@ -371,6 +1191,17 @@ impl AttributeInfo {
             },
         ))
     }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.attribute_name_index.to_be_bytes());
+
+        let body = self.attribute.write();
+        out.extend((body.len() as u32).to_be_bytes());
+        out.extend(body);
+
+        out
+    }
 }
 
 #[derive(Debug)]
@@ -382,7 +1213,7 @@ struct FieldInfo {
 }
 
 impl FieldInfo {
-    fn read<'a>(input: &'a [u8], constant_pool: &[CpInfo]) -> IResult<&'a [u8], Self> {
+    fn read<'a>(input: &'a [u8], constant_pool: ConstantPool<'_>) -> IResult<'a, Self> {
         let (input, access_flags) = FieldAccessFlags::read(input)?;
         let (input, name_index) = be_u16(input)?;
         let (input, descriptor_index) = be_u16(input)?;
@@ -402,10 +1233,22 @@ impl FieldInfo {
             },
         ))
     }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.access_flags.bits().to_be_bytes());
+        out.extend(self.name_index.to_be_bytes());
+        out.extend(self.descriptor_index.to_be_bytes());
+        out.extend((self.attributes.len() as u16).to_be_bytes());
+        for attribute in self.attributes.iter() {
+            out.extend(attribute.write());
+        }
+        out
+    }
 }
 
 bitflags! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Copy)]
     pub struct MethodAccessFlags: u16 {
         const PUBLIC = 0x0001;
         const PRIVATE = 0x0002;
@@ -423,7 +1266,7 @@ bitflags! {
 }
 
 impl MethodAccessFlags {
-    fn read(input: &[u8]) -> IResult<&[u8], Self> {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
         map(be_u16, Self::from_bits_retain)(input)
     }
 }
@@ -437,7 +1280,7 @@ struct MethodInfo {
 }
 
 impl MethodInfo {
-    fn read<'a>(input: &'a [u8], constant_pool: &[CpInfo]) -> IResult<&'a [u8], Self> {
+    fn read<'a>(input: &'a [u8], constant_pool: ConstantPool<'_>) -> IResult<'a, Self> {
         let (input, access_flags) = MethodAccessFlags::read(input)?;
         let (input, name_index) = be_u16(input)?;
         let (input, descriptor_index) = be_u16(input)?;
@@ -456,6 +1299,18 @@ impl MethodInfo {
             },
         ))
     }
+
+    fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.access_flags.bits().to_be_bytes());
+        out.extend(self.name_index.to_be_bytes());
+        out.extend(self.descriptor_index.to_be_bytes());
+        out.extend((self.attributes.len() as u16).to_be_bytes());
+        for attribute in self.attributes.iter() {
+            out.extend(attribute.write());
+        }
+        out
+    }
 }
 
 bitflags! {
@@ -469,21 +1324,56 @@ bitflags! {
         const SYNTHETIC = 0x1000;
         const ANNOTATION = 0x2000;
         const ENUM = 0x4000;
+        const MODULE = 0x8000;
     }
 }
 
 impl ClassAccessFlags {
-    fn read(input: &[u8]) -> IResult<&[u8], Self> {
+    fn read(input: &[u8]) -> IResult<'_, Self> {
         map(be_u16, Self::from_bits_retain)(input)
     }
 }
 
+/// A method resolved from a [`ClassFile`] together with its `Code` attribute,
+/// exposed so callers (e.g. [`crate::vm`]) don't need to match on the private
+/// [`MethodInfo`]/[`Attribute`] representation themselves.
+pub struct MethodView<'a> {
+    pub access_flags: MethodAccessFlags,
+    pub max_locals: u16,
+    pub code: &'a [Instruction],
+}
+
+/// An exception handler resolved from an `exception_table` entry, with `catch_type`
+/// resolved to a binary class name (`None` for a catch-all handler, `catch_type == 0`).
+pub struct ExceptionHandler<'a> {
+    pub start_pc: u16,
+    pub end_pc: u16,
+    pub handler_pc: u16,
+    pub catch_type: Option<&'a str>,
+}
+
+/// A method's `Code` attribute, resolved for disassembly.
+pub struct CodeListing<'a> {
+    pub code: &'a [Instruction],
+    pub exception_table: Vec<ExceptionHandler<'a>>,
+}
+
+/// A declared method's resolved name, descriptor, and access flags, together with its
+/// `Code` attribute if it has one, exposed so callers (e.g. [`crate::disassembler`])
+/// don't need to match on the private [`MethodInfo`]/[`Attribute`] representation
+/// themselves. `code` is `None` for an abstract or native method.
+pub struct MethodListing<'a> {
+    pub name: &'a str,
+    pub descriptor: &'a str,
+    pub access_flags: MethodAccessFlags,
+    pub code: Option<CodeListing<'a>>,
+}
+
 #[derive(Debug)]
 pub struct ClassFile {
     magic: u32,
     minor_version: u16,
     major_version: u16,
-    constant_pool_count: u16,     // TODO: required to store?
     constant_pool: Box<[CpInfo]>, // 1..(constant_pool_count - 1)
     access_flags: ClassAccessFlags,
     this_class: u16,
@@ -495,17 +1385,32 @@ pub struct ClassFile {
 }
 
 impl ClassFile {
-    pub fn read(input: &[u8]) -> IResult<&[u8], ClassFile> {
+    pub fn read(input: &[u8]) -> IResult<'_, ClassFile> {
         let (input, magic) = be_u32(input)?;
 
         let (input, minor_version) = be_u16(input)?;
         let (input, major_version) = be_u16(input)?;
 
-        let (input, constant_pool_count) = be_u16(input)?;
+        let (mut input, constant_pool_count) = be_u16(input)?;
+
+        // `Long`/`Double` entries occupy two consecutive constant-pool indices, so the
+        // logical index can run ahead of the number of entries actually parsed.
+        let mut constant_pool = Vec::with_capacity(constant_pool_count as usize);
+        constant_pool.push(CpInfo::Class { name_index: 0 });
+        let mut next_index = 1u16;
+        while next_index < constant_pool_count {
+            let (rest, entry) = CpInfo::read(input)?;
+            input = rest;
+
+            let occupies_two_slots = matches!(entry, CpInfo::Long { .. } | CpInfo::Double { .. });
+            constant_pool.push(entry);
+            next_index += 1;
 
-        let (input, mut constant_pool) =
-            count(CpInfo::read, (constant_pool_count - 1) as usize)(input)?;
-        constant_pool.insert(0, CpInfo::Class { name_index: 0 });
+            if occupies_two_slots {
+                constant_pool.push(CpInfo::Unusable);
+                next_index += 1;
+            }
+        }
 
         let (input, access_flags) = ClassAccessFlags::read(input)?;
 
@@ -515,21 +1420,23 @@ impl ClassFile {
         let (input, interfaces_count) = be_u16(input)?;
         let (input, interfaces) = count(be_u16, interfaces_count as usize)(input)?;
 
+        let pool = ConstantPool::new(&constant_pool);
+
         let (input, fields_count) = be_u16(input)?;
         let (input, fields) = count(
-            |input| FieldInfo::read(input, &constant_pool),
+            |input| FieldInfo::read(input, pool),
             fields_count as usize,
         )(input)?;
 
         let (input, methods_count) = be_u16(input)?;
         let (input, methods) = count(
-            |input| MethodInfo::read(input, &constant_pool),
+            |input| MethodInfo::read(input, pool),
             methods_count as usize,
         )(input)?;
 
         let (input, attributes_count) = be_u16(input)?;
         let (input, attributes) = count(
-            |input| AttributeInfo::read(input, &constant_pool),
+            |input| AttributeInfo::read(input, pool),
             attributes_count as usize,
         )(input)?;
 
@@ -539,7 +1446,6 @@ impl ClassFile {
                 magic,
                 minor_version,
                 major_version,
-                constant_pool_count,
                 constant_pool: constant_pool.into_boxed_slice(),
                 access_flags,
                 this_class,
@@ -551,4 +1457,139 @@ impl ClassFile {
             },
         ))
     }
+
+    /// A resolved view over this class's constant pool.
+    pub fn constant_pool(&self) -> ConstantPool<'_> {
+        ConstantPool::new(&self.constant_pool)
+    }
+
+    /// The binary name of the class described by this classfile, e.g. `java/lang/Object`.
+    pub fn this_class_name(&self) -> Result<&str, ClassFileError> {
+        self.constant_pool().class_name(self.this_class)
+    }
+
+    /// Finds a declared method by name and descriptor and returns a view of its `Code`
+    /// attribute, if it has one. Returns `Ok(None)` for an abstract or native method.
+    pub fn find_method(
+        &self,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<Option<MethodView<'_>>, ClassFileError> {
+        let pool = self.constant_pool();
+        for method in self.methods.iter() {
+            if pool.utf8(method.name_index)? != name
+                || pool.utf8(method.descriptor_index)? != descriptor
+            {
+                continue;
+            }
+
+            return Ok(method.attributes.iter().find_map(|attribute| {
+                let Attribute::Code {
+                    max_locals, code, ..
+                } = &attribute.attribute
+                else {
+                    return None;
+                };
+                Some(MethodView {
+                    access_flags: method.access_flags,
+                    max_locals: *max_locals,
+                    code,
+                })
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Resolves every declared method's name, descriptor, and `Code` attribute (if any),
+    /// in declaration order, for disassembly.
+    pub fn methods(&self) -> Result<Vec<MethodListing<'_>>, ClassFileError> {
+        let pool = self.constant_pool();
+        self.methods
+            .iter()
+            .map(|method| {
+                let code = method
+                    .attributes
+                    .iter()
+                    .find_map(|attribute| match &attribute.attribute {
+                        Attribute::Code {
+                            code,
+                            exception_table,
+                            ..
+                        } => Some((code, exception_table)),
+                        _ => None,
+                    })
+                    .map(|(code, exception_table)| {
+                        let exception_table = exception_table
+                            .iter()
+                            .map(|entry| {
+                                Ok(ExceptionHandler {
+                                    start_pc: entry.start_pc,
+                                    end_pc: entry.end_pc,
+                                    handler_pc: entry.handler_pc,
+                                    catch_type: if entry.catch_type == 0 {
+                                        None
+                                    } else {
+                                        Some(pool.class_name(entry.catch_type)?)
+                                    },
+                                })
+                            })
+                            .collect::<Result<Vec<_>, ClassFileError>>()?;
+                        Ok::<_, ClassFileError>(CodeListing {
+                            code,
+                            exception_table,
+                        })
+                    })
+                    .transpose()?;
+
+                Ok(MethodListing {
+                    name: pool.utf8(method.name_index)?,
+                    descriptor: pool.utf8(method.descriptor_index)?,
+                    access_flags: method.access_flags,
+                    code,
+                })
+            })
+            .collect()
+    }
+
+    /// Re-serializes this classfile back to `.class` bytes. `write(read(bytes).1).write()`
+    /// round-trips byte-for-byte: every constant-pool entry, access-flag set, and
+    /// attribute (including `Code`'s exception table and nested attributes, and
+    /// `Attribute::Unknown`'s raw bytes) is re-encoded rather than copied from the input.
+    pub fn write(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend(self.magic.to_be_bytes());
+        out.extend(self.minor_version.to_be_bytes());
+        out.extend(self.major_version.to_be_bytes());
+
+        out.extend((self.constant_pool.len() as u16).to_be_bytes());
+        for entry in self.constant_pool.iter().skip(1) {
+            out.extend(entry.write());
+        }
+
+        out.extend(self.access_flags.bits().to_be_bytes());
+        out.extend(self.this_class.to_be_bytes());
+        out.extend(self.super_class.to_be_bytes());
+
+        out.extend((self.interfaces.len() as u16).to_be_bytes());
+        for interface in &self.interfaces {
+            out.extend(interface.to_be_bytes());
+        }
+
+        out.extend((self.fields.len() as u16).to_be_bytes());
+        for field in self.fields.iter() {
+            out.extend(field.write());
+        }
+
+        out.extend((self.methods.len() as u16).to_be_bytes());
+        for method in self.methods.iter() {
+            out.extend(method.write());
+        }
+
+        out.extend((self.attributes.len() as u16).to_be_bytes());
+        for attribute in self.attributes.iter() {
+            out.extend(attribute.write());
+        }
+
+        out
+    }
 }
@@ -0,0 +1,149 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::error::ClassFileError;
+
+/// A JVM field descriptor: the type of a field, a method parameter, or an array element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    Byte,
+    Char,
+    Double,
+    Float,
+    Int,
+    Long,
+    Short,
+    Boolean,
+    /// `L<binary name>;`, e.g. `Ljava/lang/String;`.
+    Object(String),
+    /// One or more leading `[`, tracked as a dimension count rather than nested boxes.
+    Array {
+        dimensions: u32,
+        element: Box<FieldType>,
+    },
+}
+
+/// The return type of a method descriptor: either `void` or a [`FieldType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReturnDescriptor {
+    Void,
+    Type(FieldType),
+}
+
+/// A parsed method descriptor: its parameter types, in declaration order, and its
+/// return type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodDescriptor {
+    pub parameters: Vec<FieldType>,
+    pub return_type: ReturnDescriptor,
+}
+
+/// Parses a field descriptor, e.g. `I`, `Ljava/lang/String;`, or `[[D`.
+pub fn parse_field_type(descriptor: &str) -> Result<FieldType, ClassFileError> {
+    let mut chars = descriptor.chars().peekable();
+    let field_type = parse_one_field_type(descriptor, &mut chars)?;
+    if chars.next().is_some() {
+        return Err(ClassFileError::InvalidDescriptor {
+            descriptor: descriptor.to_string(),
+            reason: "trailing characters after field type",
+        });
+    }
+    Ok(field_type)
+}
+
+/// Parses a method descriptor, e.g. `(ILjava/lang/String;)Z`.
+pub fn parse_method_descriptor(descriptor: &str) -> Result<MethodDescriptor, ClassFileError> {
+    let malformed = |reason| ClassFileError::InvalidDescriptor {
+        descriptor: descriptor.to_string(),
+        reason,
+    };
+
+    let mut chars = descriptor.chars().peekable();
+    if chars.next() != Some('(') {
+        return Err(malformed("method descriptor must start with '('"));
+    }
+
+    let mut parameters = Vec::new();
+    loop {
+        match chars.peek() {
+            Some(')') => {
+                chars.next();
+                break;
+            }
+            Some(_) => parameters.push(parse_one_field_type(descriptor, &mut chars)?),
+            None => return Err(malformed("unterminated parameter list")),
+        }
+    }
+
+    let return_type = if chars.peek() == Some(&'V') {
+        chars.next();
+        ReturnDescriptor::Void
+    } else {
+        ReturnDescriptor::Type(parse_one_field_type(descriptor, &mut chars)?)
+    };
+
+    if chars.next().is_some() {
+        return Err(malformed("trailing characters after return type"));
+    }
+
+    Ok(MethodDescriptor {
+        parameters,
+        return_type,
+    })
+}
+
+/// Parses a single [`FieldType`] from `chars`, leaving any trailing characters (a
+/// following parameter, or garbage the caller should reject) unconsumed.
+fn parse_one_field_type(
+    descriptor: &str,
+    chars: &mut Peekable<Chars<'_>>,
+) -> Result<FieldType, ClassFileError> {
+    match chars.next() {
+        Some('B') => Ok(FieldType::Byte),
+        Some('C') => Ok(FieldType::Char),
+        Some('D') => Ok(FieldType::Double),
+        Some('F') => Ok(FieldType::Float),
+        Some('I') => Ok(FieldType::Int),
+        Some('J') => Ok(FieldType::Long),
+        Some('S') => Ok(FieldType::Short),
+        Some('Z') => Ok(FieldType::Boolean),
+        Some('L') => {
+            let mut name = String::new();
+            loop {
+                match chars.next() {
+                    Some(';') => break,
+                    Some(c) => name.push(c),
+                    None => {
+                        return Err(ClassFileError::InvalidDescriptor {
+                            descriptor: descriptor.to_string(),
+                            reason: "unterminated object type",
+                        })
+                    }
+                }
+            }
+            Ok(FieldType::Object(name))
+        }
+        Some('[') => {
+            let mut dimensions = 1u32;
+            while chars.peek() == Some(&'[') {
+                chars.next();
+                dimensions += 1;
+            }
+            if chars.peek().is_none() {
+                return Err(ClassFileError::InvalidDescriptor {
+                    descriptor: descriptor.to_string(),
+                    reason: "array with no element type",
+                });
+            }
+            let element = parse_one_field_type(descriptor, chars)?;
+            Ok(FieldType::Array {
+                dimensions,
+                element: Box::new(element),
+            })
+        }
+        _ => Err(ClassFileError::InvalidDescriptor {
+            descriptor: descriptor.to_string(),
+            reason: "unknown field type tag",
+        }),
+    }
+}
@@ -0,0 +1,426 @@
+use crate::classfile::{ClassFile, ConstantPool};
+use crate::error::ClassFileError;
+use crate::instruction::{Instruction, Wide};
+
+/// Renders a [`ClassFile`] as `javap`-style disassembly text: each declared method's
+/// access flags, name, and descriptor, followed by its `Code` attribute rendered one
+/// instruction per line as `offset: mnemonic operands`, with branch targets resolved to
+/// absolute offsets, constant-pool references resolved to symbolic form, and the
+/// exception table listed underneath.
+pub fn disassemble(class: &ClassFile) -> Result<String, ClassFileError> {
+    let pool = class.constant_pool();
+    let mut out = String::new();
+
+    for method in class.methods()? {
+        out.push_str(&format!(
+            "{:?} {} {}\n",
+            method.access_flags, method.name, method.descriptor
+        ));
+
+        let Some(code) = method.code else {
+            out.push('\n');
+            continue;
+        };
+
+        for (offset, instruction) in Instruction::with_offsets(code.code) {
+            out.push_str(&format!(
+                "  {offset}: {}\n",
+                render_instruction(instruction, offset as usize, pool)
+            ));
+        }
+
+        if !code.exception_table.is_empty() {
+            out.push_str("  Exception table:\n");
+            for handler in code.exception_table.iter() {
+                let catch_type = handler.catch_type.unwrap_or("any");
+                out.push_str(&format!(
+                    "    from {} to {} target {} type {catch_type}\n",
+                    handler.start_pc, handler.end_pc, handler.handler_pc
+                ));
+            }
+        }
+
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+/// Resolves a branch instruction's offset (relative to its own opcode byte) to the
+/// absolute byte offset of its target.
+fn branch_target(offset: usize, relative: i32) -> i64 {
+    offset as i64 + relative as i64
+}
+
+/// Resolves a constant-pool entry referenced by `ldc`/`ldc_w`/`ldc2_w` to a literal
+/// rendering, falling back to a raw index if the entry's kind isn't one `ldc*` can load.
+fn describe_loadable_constant(pool: ConstantPool<'_>, index: u16) -> String {
+    if let Ok(value) = pool.integer(index) {
+        return value.to_string();
+    }
+    if let Ok(value) = pool.float(index) {
+        return format!("{value}f");
+    }
+    if let Ok(value) = pool.long(index) {
+        return format!("{value}l");
+    }
+    if let Ok(value) = pool.double(index) {
+        return format!("{value}d");
+    }
+    if let Ok(value) = pool.string(index) {
+        return format!("{value:?}");
+    }
+    if let Ok(value) = pool.class_name(index) {
+        return format!("{value}.class");
+    }
+    format!("#{index}")
+}
+
+fn describe_method(pool: ConstantPool<'_>, index: u16) -> String {
+    match pool.method_ref(index) {
+        Ok((class_name, name, descriptor)) => format!("{class_name}.{name}:{descriptor}"),
+        Err(_) => format!("#{index}"),
+    }
+}
+
+fn describe_field(pool: ConstantPool<'_>, index: u16) -> String {
+    match pool.field_ref(index) {
+        Ok((class_name, name, descriptor)) => format!("{class_name}.{name}:{descriptor}"),
+        Err(_) => format!("#{index}"),
+    }
+}
+
+fn describe_class(pool: ConstantPool<'_>, index: u16) -> String {
+    pool.class_name(index)
+        .map(str::to_string)
+        .unwrap_or_else(|_| format!("#{index}"))
+}
+
+/// The array element type encoded in a `newarray`'s `atype` operand.
+fn array_type_name(atype: u8) -> &'static str {
+    match atype {
+        4 => "boolean",
+        5 => "char",
+        6 => "float",
+        7 => "double",
+        8 => "byte",
+        9 => "short",
+        10 => "int",
+        11 => "long",
+        _ => "unknown",
+    }
+}
+
+fn render_wide(wide: &Wide) -> String {
+    match wide {
+        Wide::Iload { index } => format!("iload {index}"),
+        Wide::Lload { index } => format!("lload {index}"),
+        Wide::Fload { index } => format!("fload {index}"),
+        Wide::Dload { index } => format!("dload {index}"),
+        Wide::Aload { index } => format!("aload {index}"),
+        Wide::Istore { index } => format!("istore {index}"),
+        Wide::Lstore { index } => format!("lstore {index}"),
+        Wide::Fstore { index } => format!("fstore {index}"),
+        Wide::Dstore { index } => format!("dstore {index}"),
+        Wide::Astore { index } => format!("astore {index}"),
+        Wide::Ret { index } => format!("ret {index}"),
+        Wide::Iinc { index, value } => format!("iinc {index}, {value}"),
+    }
+}
+
+fn render_instruction(instruction: &Instruction, offset: usize, pool: ConstantPool<'_>) -> String {
+    match instruction {
+        Instruction::Nop => "nop".to_string(),
+        Instruction::AconstNull => "aconst_null".to_string(),
+        Instruction::IconstM1 => "iconst_m1".to_string(),
+        Instruction::Iconst0 => "iconst_0".to_string(),
+        Instruction::Iconst1 => "iconst_1".to_string(),
+        Instruction::Iconst2 => "iconst_2".to_string(),
+        Instruction::Iconst3 => "iconst_3".to_string(),
+        Instruction::Iconst4 => "iconst_4".to_string(),
+        Instruction::Iconst5 => "iconst_5".to_string(),
+        Instruction::Lconst0 => "lconst_0".to_string(),
+        Instruction::Lconst1 => "lconst_1".to_string(),
+        Instruction::Fconst0 => "fconst_0".to_string(),
+        Instruction::Fconst1 => "fconst_1".to_string(),
+        Instruction::Fconst2 => "fconst_2".to_string(),
+        Instruction::Dconst0 => "dconst_0".to_string(),
+        Instruction::Dconst1 => "dconst_1".to_string(),
+        Instruction::Bipush { value } => format!("bipush {value}"),
+        Instruction::Sipush { value } => format!("sipush {value}"),
+        Instruction::Ldc { index } => {
+            format!("ldc {}", describe_loadable_constant(pool, *index as u16))
+        }
+        Instruction::LdcW { index } => {
+            format!("ldc_w {}", describe_loadable_constant(pool, *index))
+        }
+        Instruction::Ldc2W { index } => {
+            format!("ldc2_w {}", describe_loadable_constant(pool, *index))
+        }
+        Instruction::Iload { index } => format!("iload {index}"),
+        Instruction::Lload { index } => format!("lload {index}"),
+        Instruction::Fload { index } => format!("fload {index}"),
+        Instruction::Dload { index } => format!("dload {index}"),
+        Instruction::Aload { index } => format!("aload {index}"),
+        Instruction::Iload0 => "iload_0".to_string(),
+        Instruction::Iload1 => "iload_1".to_string(),
+        Instruction::Iload2 => "iload_2".to_string(),
+        Instruction::Iload3 => "iload_3".to_string(),
+        Instruction::Lload0 => "lload_0".to_string(),
+        Instruction::Lload1 => "lload_1".to_string(),
+        Instruction::Lload2 => "lload_2".to_string(),
+        Instruction::Lload3 => "lload_3".to_string(),
+        Instruction::Fload0 => "fload_0".to_string(),
+        Instruction::Fload1 => "fload_1".to_string(),
+        Instruction::Fload2 => "fload_2".to_string(),
+        Instruction::Fload3 => "fload_3".to_string(),
+        Instruction::Dload0 => "dload_0".to_string(),
+        Instruction::Dload1 => "dload_1".to_string(),
+        Instruction::Dload2 => "dload_2".to_string(),
+        Instruction::Dload3 => "dload_3".to_string(),
+        Instruction::Aload0 => "aload_0".to_string(),
+        Instruction::Aload1 => "aload_1".to_string(),
+        Instruction::Aload2 => "aload_2".to_string(),
+        Instruction::Aload3 => "aload_3".to_string(),
+        Instruction::Iaload => "iaload".to_string(),
+        Instruction::Laload => "laload".to_string(),
+        Instruction::Faload => "faload".to_string(),
+        Instruction::Daload => "daload".to_string(),
+        Instruction::Aaload => "aaload".to_string(),
+        Instruction::Baload => "baload".to_string(),
+        Instruction::Caload => "caload".to_string(),
+        Instruction::Saload => "saload".to_string(),
+        Instruction::Istore { index } => format!("istore {index}"),
+        Instruction::Lstore { index } => format!("lstore {index}"),
+        Instruction::Fstore { index } => format!("fstore {index}"),
+        Instruction::Dstore { index } => format!("dstore {index}"),
+        Instruction::Astore { index } => format!("astore {index}"),
+        Instruction::Istore0 => "istore_0".to_string(),
+        Instruction::Istore1 => "istore_1".to_string(),
+        Instruction::Istore2 => "istore_2".to_string(),
+        Instruction::Istore3 => "istore_3".to_string(),
+        Instruction::Lstore0 => "lstore_0".to_string(),
+        Instruction::Lstore1 => "lstore_1".to_string(),
+        Instruction::Lstore2 => "lstore_2".to_string(),
+        Instruction::Lstore3 => "lstore_3".to_string(),
+        Instruction::Fstore0 => "fstore_0".to_string(),
+        Instruction::Fstore1 => "fstore_1".to_string(),
+        Instruction::Fstore2 => "fstore_2".to_string(),
+        Instruction::Fstore3 => "fstore_3".to_string(),
+        Instruction::Dstore0 => "dstore_0".to_string(),
+        Instruction::Dstore1 => "dstore_1".to_string(),
+        Instruction::Dstore2 => "dstore_2".to_string(),
+        Instruction::Dstore3 => "dstore_3".to_string(),
+        Instruction::Astore0 => "astore_0".to_string(),
+        Instruction::Astore1 => "astore_1".to_string(),
+        Instruction::Astore2 => "astore_2".to_string(),
+        Instruction::Astore3 => "astore_3".to_string(),
+        Instruction::Iastore => "iastore".to_string(),
+        Instruction::Lastore => "lastore".to_string(),
+        Instruction::Fastore => "fastore".to_string(),
+        Instruction::Dastore => "dastore".to_string(),
+        Instruction::Aastore => "aastore".to_string(),
+        Instruction::Bastore => "bastore".to_string(),
+        Instruction::Castore => "castore".to_string(),
+        Instruction::Sastore => "sastore".to_string(),
+        Instruction::Pop => "pop".to_string(),
+        Instruction::Pop2 => "pop2".to_string(),
+        Instruction::Dup => "dup".to_string(),
+        Instruction::DupX1 => "dup_x1".to_string(),
+        Instruction::DupX2 => "dup_x2".to_string(),
+        Instruction::Dup2 => "dup2".to_string(),
+        Instruction::Dup2X1 => "dup2_x1".to_string(),
+        Instruction::Dup2X2 => "dup2_x2".to_string(),
+        Instruction::Swap => "swap".to_string(),
+        Instruction::Iadd => "iadd".to_string(),
+        Instruction::Ladd => "ladd".to_string(),
+        Instruction::Fadd => "fadd".to_string(),
+        Instruction::Dadd => "dadd".to_string(),
+        Instruction::Isub => "isub".to_string(),
+        Instruction::Lsub => "lsub".to_string(),
+        Instruction::Fsub => "fsub".to_string(),
+        Instruction::Dsub => "dsub".to_string(),
+        Instruction::Imul => "imul".to_string(),
+        Instruction::Lmul => "lmul".to_string(),
+        Instruction::Fmul => "fmul".to_string(),
+        Instruction::Dmul => "dmul".to_string(),
+        Instruction::Idiv => "idiv".to_string(),
+        Instruction::Ldiv => "ldiv".to_string(),
+        Instruction::Fdiv => "fdiv".to_string(),
+        Instruction::Ddiv => "ddiv".to_string(),
+        Instruction::Irem => "irem".to_string(),
+        Instruction::Lrem => "lrem".to_string(),
+        Instruction::Frem => "frem".to_string(),
+        Instruction::Drem => "drem".to_string(),
+        Instruction::Ineg => "ineg".to_string(),
+        Instruction::Lneg => "lneg".to_string(),
+        Instruction::Fneg => "fneg".to_string(),
+        Instruction::Dneg => "dneg".to_string(),
+        Instruction::Ishl => "ishl".to_string(),
+        Instruction::Lshl => "lshl".to_string(),
+        Instruction::Ishr => "ishr".to_string(),
+        Instruction::Lshr => "lshr".to_string(),
+        Instruction::Iushr => "iushr".to_string(),
+        Instruction::Lushr => "lushr".to_string(),
+        Instruction::Iand => "iand".to_string(),
+        Instruction::Land => "land".to_string(),
+        Instruction::Ior => "ior".to_string(),
+        Instruction::Lor => "lor".to_string(),
+        Instruction::Ixor => "ixor".to_string(),
+        Instruction::Lxor => "lxor".to_string(),
+        Instruction::Iinc { index, value } => format!("iinc {index}, {value}"),
+        Instruction::I2l => "i2l".to_string(),
+        Instruction::I2f => "i2f".to_string(),
+        Instruction::I2d => "i2d".to_string(),
+        Instruction::L2i => "l2i".to_string(),
+        Instruction::L2f => "l2f".to_string(),
+        Instruction::L2d => "l2d".to_string(),
+        Instruction::F2i => "f2i".to_string(),
+        Instruction::F2l => "f2l".to_string(),
+        Instruction::F2d => "f2d".to_string(),
+        Instruction::D2i => "d2i".to_string(),
+        Instruction::D2l => "d2l".to_string(),
+        Instruction::D2f => "d2f".to_string(),
+        Instruction::I2b => "i2b".to_string(),
+        Instruction::I2c => "i2c".to_string(),
+        Instruction::I2s => "i2s".to_string(),
+        Instruction::Lcmp => "lcmp".to_string(),
+        Instruction::Fcmpl => "fcmpl".to_string(),
+        Instruction::Fcmpg => "fcmpg".to_string(),
+        Instruction::Dcmpl => "dcmpl".to_string(),
+        Instruction::Dcmpg => "dcmpg".to_string(),
+        Instruction::Ifeq { offset: rel } => {
+            format!("ifeq {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Ifne { offset: rel } => {
+            format!("ifne {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Iflt { offset: rel } => {
+            format!("iflt {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Ifge { offset: rel } => {
+            format!("ifge {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Ifgt { offset: rel } => {
+            format!("ifgt {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Ifle { offset: rel } => {
+            format!("ifle {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfIcmpeq { offset: rel } => {
+            format!("if_icmpeq {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfIcmpne { offset: rel } => {
+            format!("if_icmpne {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfIcmplt { offset: rel } => {
+            format!("if_icmplt {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfIcmpge { offset: rel } => {
+            format!("if_icmpge {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfIcmpgt { offset: rel } => {
+            format!("if_icmpgt {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfIcmple { offset: rel } => {
+            format!("if_icmple {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfAcmpeq { offset: rel } => {
+            format!("if_acmpeq {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::IfAcmpne { offset: rel } => {
+            format!("if_acmpne {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Goto { offset: rel } => {
+            format!("goto {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Jsr { offset: rel } => {
+            format!("jsr {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Ret { index } => format!("ret {index}"),
+        Instruction::Tableswitch {
+            default,
+            low,
+            high,
+            jump_offsets,
+        } => {
+            let mut rendered = format!("tableswitch {{ // {low} to {high}\n");
+            for (case, rel) in (*low..=*high).zip(jump_offsets.iter()) {
+                rendered.push_str(&format!(
+                    "        {case}: {}\n",
+                    branch_target(offset, *rel)
+                ));
+            }
+            rendered.push_str(&format!(
+                "        default: {}\n      }}",
+                branch_target(offset, *default)
+            ));
+            rendered
+        }
+        Instruction::Lookupswitch { default, pairs } => {
+            let mut rendered = "lookupswitch {\n".to_string();
+            for (value, rel) in pairs.iter() {
+                rendered.push_str(&format!(
+                    "        {value}: {}\n",
+                    branch_target(offset, *rel)
+                ));
+            }
+            rendered.push_str(&format!(
+                "        default: {}\n      }}",
+                branch_target(offset, *default)
+            ));
+            rendered
+        }
+        Instruction::Ireturn => "ireturn".to_string(),
+        Instruction::Lreturn => "lreturn".to_string(),
+        Instruction::Freturn => "freturn".to_string(),
+        Instruction::Dreturn => "dreturn".to_string(),
+        Instruction::Areturn => "areturn".to_string(),
+        Instruction::Return => "return".to_string(),
+        Instruction::Getstatic { index } => format!("getstatic {}", describe_field(pool, *index)),
+        Instruction::Putstatic { index } => format!("putstatic {}", describe_field(pool, *index)),
+        Instruction::Getfield { index } => format!("getfield {}", describe_field(pool, *index)),
+        Instruction::Putfield { index } => format!("putfield {}", describe_field(pool, *index)),
+        Instruction::Invokevirtual { index } => {
+            format!("invokevirtual {}", describe_method(pool, *index))
+        }
+        Instruction::Invokespecial { index } => {
+            format!("invokespecial {}", describe_method(pool, *index))
+        }
+        Instruction::Invokestatic { index } => {
+            format!("invokestatic {}", describe_method(pool, *index))
+        }
+        Instruction::Invokeinterface { index, count } => {
+            format!("invokeinterface {}, {count}", describe_method(pool, *index))
+        }
+        Instruction::Invokedynamic { index } => format!("invokedynamic #{index}"),
+        Instruction::New { index } => format!("new {}", describe_class(pool, *index)),
+        Instruction::Newarray { atype } => format!("newarray {}", array_type_name(*atype)),
+        Instruction::Anewarray { index } => {
+            format!("anewarray {}", describe_class(pool, *index))
+        }
+        Instruction::Arraylength => "arraylength".to_string(),
+        Instruction::Athrow => "athrow".to_string(),
+        Instruction::Checkcast { index } => format!("checkcast {}", describe_class(pool, *index)),
+        Instruction::Instanceof { index } => {
+            format!("instanceof {}", describe_class(pool, *index))
+        }
+        Instruction::Monitorenter => "monitorenter".to_string(),
+        Instruction::Monitorexit => "monitorexit".to_string(),
+        Instruction::Wide(wide) => format!("wide {}", render_wide(wide)),
+        Instruction::Multianewarray { index, dimensions } => {
+            format!(
+                "multianewarray {}, {dimensions}",
+                describe_class(pool, *index)
+            )
+        }
+        Instruction::Ifnull { offset: rel } => {
+            format!("ifnull {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::Ifnonnull { offset: rel } => {
+            format!("ifnonnull {}", branch_target(offset, *rel as i32))
+        }
+        Instruction::GotoW { offset: rel } => format!("goto_w {}", branch_target(offset, *rel)),
+        Instruction::JsrW { offset: rel } => format!("jsr_w {}", branch_target(offset, *rel)),
+    }
+}
@@ -0,0 +1,70 @@
+use nom::error::ErrorKind;
+
+/// Crate-wide error for malformed classfile input, carried through `nom`'s
+/// custom-error mechanism instead of panicking on unexpected bytes.
+#[derive(Debug, Clone)]
+pub enum ClassFileError {
+    UnknownConstantTag(u8),
+    UnknownOpcode(u8),
+    InvalidModifiedUtf8 { bytes: Vec<u8> },
+    ConstantPoolIndexOutOfRange(u16),
+    ConstantPoolTypeMismatch {
+        index: u16,
+        expected: &'static str,
+        found: &'static str,
+    },
+    UnknownVerificationTypeTag(u8),
+    ReservedStackMapFrameType(u8),
+    InvalidDescriptor {
+        descriptor: String,
+        reason: &'static str,
+    },
+    Nom(ErrorKind),
+}
+
+impl std::fmt::Display for ClassFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClassFileError::UnknownConstantTag(tag) => {
+                write!(f, "unknown constant pool tag: {tag}")
+            }
+            ClassFileError::UnknownOpcode(opcode) => write!(f, "unknown opcode: {opcode}"),
+            ClassFileError::InvalidModifiedUtf8 { bytes } => {
+                write!(f, "invalid modified UTF-8 bytes: {bytes:?}")
+            }
+            ClassFileError::ConstantPoolIndexOutOfRange(index) => {
+                write!(f, "constant pool index {index} is out of range")
+            }
+            ClassFileError::ConstantPoolTypeMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "constant pool entry {index} is a {found}, expected a {expected}"
+            ),
+            ClassFileError::UnknownVerificationTypeTag(tag) => {
+                write!(f, "unknown verification_type_info tag: {tag}")
+            }
+            ClassFileError::ReservedStackMapFrameType(frame_type) => {
+                write!(f, "reserved stack_map_frame frame_type: {frame_type}")
+            }
+            ClassFileError::InvalidDescriptor { descriptor, reason } => {
+                write!(f, "invalid descriptor {descriptor:?}: {reason}")
+            }
+            ClassFileError::Nom(kind) => write!(f, "parse error: {kind:?}"),
+        }
+    }
+}
+
+impl std::error::Error for ClassFileError {}
+
+impl<'a> nom::error::ParseError<&'a [u8]> for ClassFileError {
+    fn from_error_kind(_input: &'a [u8], kind: ErrorKind) -> Self {
+        ClassFileError::Nom(kind)
+    }
+
+    fn append(_input: &'a [u8], _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
@@ -1,37 +1,1180 @@
-use nom::IResult;
+use nom::{
+    bytes::complete::take,
+    number::complete::{be_i16, be_i32, be_i8, be_u16, be_u8},
+};
+
+use crate::error::ClassFileError;
+
+type IResult<'a, T> = nom::IResult<&'a [u8], T, ClassFileError>;
+
+#[derive(Debug)]
+pub enum Wide {
+    Iload { index: u16 },
+    Lload { index: u16 },
+    Fload { index: u16 },
+    Dload { index: u16 },
+    Aload { index: u16 },
+    Istore { index: u16 },
+    Lstore { index: u16 },
+    Fstore { index: u16 },
+    Dstore { index: u16 },
+    Astore { index: u16 },
+    Ret { index: u16 },
+    Iinc { index: u16, value: i16 },
+}
 
 #[derive(Debug)]
 #[repr(u8)]
 pub enum Instruction {
+    Nop = 0,
+    AconstNull = 1,
+    IconstM1 = 2,
+    Iconst0 = 3,
+    Iconst1 = 4,
+    Iconst2 = 5,
+    Iconst3 = 6,
+    Iconst4 = 7,
+    Iconst5 = 8,
+    Lconst0 = 9,
+    Lconst1 = 10,
+    Fconst0 = 11,
+    Fconst1 = 12,
+    Fconst2 = 13,
+    Dconst0 = 14,
+    Dconst1 = 15,
+    Bipush { value: i8 } = 16,
+    Sipush { value: i16 } = 17,
+    Ldc { index: u8 } = 18,
+    LdcW { index: u16 } = 19,
+    Ldc2W { index: u16 } = 20,
+    Iload { index: u8 } = 21,
+    Lload { index: u8 } = 22,
+    Fload { index: u8 } = 23,
+    Dload { index: u8 } = 24,
+    Aload { index: u8 } = 25,
+    Iload0 = 26,
+    Iload1 = 27,
+    Iload2 = 28,
+    Iload3 = 29,
+    Lload0 = 30,
+    Lload1 = 31,
+    Lload2 = 32,
+    Lload3 = 33,
+    Fload0 = 34,
+    Fload1 = 35,
+    Fload2 = 36,
+    Fload3 = 37,
+    Dload0 = 38,
+    Dload1 = 39,
+    Dload2 = 40,
+    Dload3 = 41,
     Aload0 = 42,
     Aload1 = 43,
     Aload2 = 44,
     Aload3 = 45,
+    Iaload = 46,
+    Laload = 47,
+    Faload = 48,
+    Daload = 49,
+    Aaload = 50,
+    Baload = 51,
+    Caload = 52,
+    Saload = 53,
+    Istore { index: u8 } = 54,
+    Lstore { index: u8 } = 55,
+    Fstore { index: u8 } = 56,
+    Dstore { index: u8 } = 57,
+    Astore { index: u8 } = 58,
+    Istore0 = 59,
+    Istore1 = 60,
+    Istore2 = 61,
+    Istore3 = 62,
+    Lstore0 = 63,
+    Lstore1 = 64,
+    Lstore2 = 65,
+    Lstore3 = 66,
+    Fstore0 = 67,
+    Fstore1 = 68,
+    Fstore2 = 69,
+    Fstore3 = 70,
+    Dstore0 = 71,
+    Dstore1 = 72,
+    Dstore2 = 73,
+    Dstore3 = 74,
+    Astore0 = 75,
+    Astore1 = 76,
+    Astore2 = 77,
+    Astore3 = 78,
+    Iastore = 79,
+    Lastore = 80,
+    Fastore = 81,
+    Dastore = 82,
+    Aastore = 83,
+    Bastore = 84,
+    Castore = 85,
+    Sastore = 86,
+    Pop = 87,
+    Pop2 = 88,
+    Dup = 89,
+    DupX1 = 90,
+    DupX2 = 91,
+    Dup2 = 92,
+    Dup2X1 = 93,
+    Dup2X2 = 94,
+    Swap = 95,
+    Iadd = 96,
+    Ladd = 97,
+    Fadd = 98,
+    Dadd = 99,
+    Isub = 100,
+    Lsub = 101,
+    Fsub = 102,
+    Dsub = 103,
+    Imul = 104,
+    Lmul = 105,
+    Fmul = 106,
+    Dmul = 107,
+    Idiv = 108,
+    Ldiv = 109,
+    Fdiv = 110,
+    Ddiv = 111,
+    Irem = 112,
+    Lrem = 113,
+    Frem = 114,
+    Drem = 115,
+    Ineg = 116,
+    Lneg = 117,
+    Fneg = 118,
+    Dneg = 119,
+    Ishl = 120,
+    Lshl = 121,
+    Ishr = 122,
+    Lshr = 123,
+    Iushr = 124,
+    Lushr = 125,
+    Iand = 126,
+    Land = 127,
+    Ior = 128,
+    Lor = 129,
+    Ixor = 130,
+    Lxor = 131,
+    Iinc { index: u8, value: i8 } = 132,
+    I2l = 133,
+    I2f = 134,
+    I2d = 135,
+    L2i = 136,
+    L2f = 137,
+    L2d = 138,
+    F2i = 139,
+    F2l = 140,
+    F2d = 141,
+    D2i = 142,
+    D2l = 143,
+    D2f = 144,
+    I2b = 145,
+    I2c = 146,
+    I2s = 147,
+    Lcmp = 148,
+    Fcmpl = 149,
+    Fcmpg = 150,
+    Dcmpl = 151,
+    Dcmpg = 152,
+    Ifeq { offset: i16 } = 153,
+    Ifne { offset: i16 } = 154,
+    Iflt { offset: i16 } = 155,
+    Ifge { offset: i16 } = 156,
+    Ifgt { offset: i16 } = 157,
+    Ifle { offset: i16 } = 158,
+    IfIcmpeq { offset: i16 } = 159,
+    IfIcmpne { offset: i16 } = 160,
+    IfIcmplt { offset: i16 } = 161,
+    IfIcmpge { offset: i16 } = 162,
+    IfIcmpgt { offset: i16 } = 163,
+    IfIcmple { offset: i16 } = 164,
+    IfAcmpeq { offset: i16 } = 165,
+    IfAcmpne { offset: i16 } = 166,
+    Goto { offset: i16 } = 167,
+    Jsr { offset: i16 } = 168,
+    Ret { index: u8 } = 169,
+    Tableswitch {
+        default: i32,
+        low: i32,
+        high: i32,
+        jump_offsets: Box<[i32]>,
+    } = 170,
+    Lookupswitch {
+        default: i32,
+        pairs: Box<[(i32, i32)]>,
+    } = 171,
+    Ireturn = 172,
+    Lreturn = 173,
+    Freturn = 174,
+    Dreturn = 175,
+    Areturn = 176,
     Return = 177,
-    Invokespecial { indexbyte1: u8, indexbyte2: u8 } = 183,
+    Getstatic { index: u16 } = 178,
+    Putstatic { index: u16 } = 179,
+    Getfield { index: u16 } = 180,
+    Putfield { index: u16 } = 181,
+    Invokevirtual { index: u16 } = 182,
+    Invokespecial { index: u16 } = 183,
+    Invokestatic { index: u16 } = 184,
+    Invokeinterface { index: u16, count: u8 } = 185,
+    Invokedynamic { index: u16 } = 186,
+    New { index: u16 } = 187,
+    Newarray { atype: u8 } = 188,
+    Anewarray { index: u16 } = 189,
+    Arraylength = 190,
+    Athrow = 191,
+    Checkcast { index: u16 } = 192,
+    Instanceof { index: u16 } = 193,
+    Monitorenter = 194,
+    Monitorexit = 195,
+    Wide(Wide) = 196,
+    Multianewarray { index: u16, dimensions: u8 } = 197,
+    Ifnull { offset: i16 } = 198,
+    Ifnonnull { offset: i16 } = 199,
+    GotoW { offset: i32 } = 200,
+    JsrW { offset: i32 } = 201,
 }
 
 impl Instruction {
-    pub fn read(input: &[u8]) -> IResult<&[u8], Self> {
-        let (input, instruction) = nom::number::complete::u8(input)?;
-        match instruction {
-            42 => Ok((input, Instruction::Aload2)),
+    /// `offset` is the position of the opcode byte within the method's `code` array,
+    /// needed to compute the padding for `tableswitch`/`lookupswitch`.
+    pub fn read(input: &[u8], offset: usize) -> IResult<'_, Self> {
+        let (input, opcode) = be_u8(input)?;
+        match opcode {
+            0 => Ok((input, Instruction::Nop)),
+            1 => Ok((input, Instruction::AconstNull)),
+            2 => Ok((input, Instruction::IconstM1)),
+            3 => Ok((input, Instruction::Iconst0)),
+            4 => Ok((input, Instruction::Iconst1)),
+            5 => Ok((input, Instruction::Iconst2)),
+            6 => Ok((input, Instruction::Iconst3)),
+            7 => Ok((input, Instruction::Iconst4)),
+            8 => Ok((input, Instruction::Iconst5)),
+            9 => Ok((input, Instruction::Lconst0)),
+            10 => Ok((input, Instruction::Lconst1)),
+            11 => Ok((input, Instruction::Fconst0)),
+            12 => Ok((input, Instruction::Fconst1)),
+            13 => Ok((input, Instruction::Fconst2)),
+            14 => Ok((input, Instruction::Dconst0)),
+            15 => Ok((input, Instruction::Dconst1)),
+            16 => {
+                let (input, value) = be_i8(input)?;
+                Ok((input, Instruction::Bipush { value }))
+            }
+            17 => {
+                let (input, value) = be_i16(input)?;
+                Ok((input, Instruction::Sipush { value }))
+            }
+            18 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Ldc { index }))
+            }
+            19 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::LdcW { index }))
+            }
+            20 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Ldc2W { index }))
+            }
+            21 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Iload { index }))
+            }
+            22 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Lload { index }))
+            }
+            23 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Fload { index }))
+            }
+            24 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Dload { index }))
+            }
+            25 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Aload { index }))
+            }
+            26 => Ok((input, Instruction::Iload0)),
+            27 => Ok((input, Instruction::Iload1)),
+            28 => Ok((input, Instruction::Iload2)),
+            29 => Ok((input, Instruction::Iload3)),
+            30 => Ok((input, Instruction::Lload0)),
+            31 => Ok((input, Instruction::Lload1)),
+            32 => Ok((input, Instruction::Lload2)),
+            33 => Ok((input, Instruction::Lload3)),
+            34 => Ok((input, Instruction::Fload0)),
+            35 => Ok((input, Instruction::Fload1)),
+            36 => Ok((input, Instruction::Fload2)),
+            37 => Ok((input, Instruction::Fload3)),
+            38 => Ok((input, Instruction::Dload0)),
+            39 => Ok((input, Instruction::Dload1)),
+            40 => Ok((input, Instruction::Dload2)),
+            41 => Ok((input, Instruction::Dload3)),
+            42 => Ok((input, Instruction::Aload0)),
             43 => Ok((input, Instruction::Aload1)),
             44 => Ok((input, Instruction::Aload2)),
             45 => Ok((input, Instruction::Aload3)),
+            46 => Ok((input, Instruction::Iaload)),
+            47 => Ok((input, Instruction::Laload)),
+            48 => Ok((input, Instruction::Faload)),
+            49 => Ok((input, Instruction::Daload)),
+            50 => Ok((input, Instruction::Aaload)),
+            51 => Ok((input, Instruction::Baload)),
+            52 => Ok((input, Instruction::Caload)),
+            53 => Ok((input, Instruction::Saload)),
+            54 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Istore { index }))
+            }
+            55 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Lstore { index }))
+            }
+            56 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Fstore { index }))
+            }
+            57 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Dstore { index }))
+            }
+            58 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Astore { index }))
+            }
+            59 => Ok((input, Instruction::Istore0)),
+            60 => Ok((input, Instruction::Istore1)),
+            61 => Ok((input, Instruction::Istore2)),
+            62 => Ok((input, Instruction::Istore3)),
+            63 => Ok((input, Instruction::Lstore0)),
+            64 => Ok((input, Instruction::Lstore1)),
+            65 => Ok((input, Instruction::Lstore2)),
+            66 => Ok((input, Instruction::Lstore3)),
+            67 => Ok((input, Instruction::Fstore0)),
+            68 => Ok((input, Instruction::Fstore1)),
+            69 => Ok((input, Instruction::Fstore2)),
+            70 => Ok((input, Instruction::Fstore3)),
+            71 => Ok((input, Instruction::Dstore0)),
+            72 => Ok((input, Instruction::Dstore1)),
+            73 => Ok((input, Instruction::Dstore2)),
+            74 => Ok((input, Instruction::Dstore3)),
+            75 => Ok((input, Instruction::Astore0)),
+            76 => Ok((input, Instruction::Astore1)),
+            77 => Ok((input, Instruction::Astore2)),
+            78 => Ok((input, Instruction::Astore3)),
+            79 => Ok((input, Instruction::Iastore)),
+            80 => Ok((input, Instruction::Lastore)),
+            81 => Ok((input, Instruction::Fastore)),
+            82 => Ok((input, Instruction::Dastore)),
+            83 => Ok((input, Instruction::Aastore)),
+            84 => Ok((input, Instruction::Bastore)),
+            85 => Ok((input, Instruction::Castore)),
+            86 => Ok((input, Instruction::Sastore)),
+            87 => Ok((input, Instruction::Pop)),
+            88 => Ok((input, Instruction::Pop2)),
+            89 => Ok((input, Instruction::Dup)),
+            90 => Ok((input, Instruction::DupX1)),
+            91 => Ok((input, Instruction::DupX2)),
+            92 => Ok((input, Instruction::Dup2)),
+            93 => Ok((input, Instruction::Dup2X1)),
+            94 => Ok((input, Instruction::Dup2X2)),
+            95 => Ok((input, Instruction::Swap)),
+            96 => Ok((input, Instruction::Iadd)),
+            97 => Ok((input, Instruction::Ladd)),
+            98 => Ok((input, Instruction::Fadd)),
+            99 => Ok((input, Instruction::Dadd)),
+            100 => Ok((input, Instruction::Isub)),
+            101 => Ok((input, Instruction::Lsub)),
+            102 => Ok((input, Instruction::Fsub)),
+            103 => Ok((input, Instruction::Dsub)),
+            104 => Ok((input, Instruction::Imul)),
+            105 => Ok((input, Instruction::Lmul)),
+            106 => Ok((input, Instruction::Fmul)),
+            107 => Ok((input, Instruction::Dmul)),
+            108 => Ok((input, Instruction::Idiv)),
+            109 => Ok((input, Instruction::Ldiv)),
+            110 => Ok((input, Instruction::Fdiv)),
+            111 => Ok((input, Instruction::Ddiv)),
+            112 => Ok((input, Instruction::Irem)),
+            113 => Ok((input, Instruction::Lrem)),
+            114 => Ok((input, Instruction::Frem)),
+            115 => Ok((input, Instruction::Drem)),
+            116 => Ok((input, Instruction::Ineg)),
+            117 => Ok((input, Instruction::Lneg)),
+            118 => Ok((input, Instruction::Fneg)),
+            119 => Ok((input, Instruction::Dneg)),
+            120 => Ok((input, Instruction::Ishl)),
+            121 => Ok((input, Instruction::Lshl)),
+            122 => Ok((input, Instruction::Ishr)),
+            123 => Ok((input, Instruction::Lshr)),
+            124 => Ok((input, Instruction::Iushr)),
+            125 => Ok((input, Instruction::Lushr)),
+            126 => Ok((input, Instruction::Iand)),
+            127 => Ok((input, Instruction::Land)),
+            128 => Ok((input, Instruction::Ior)),
+            129 => Ok((input, Instruction::Lor)),
+            130 => Ok((input, Instruction::Ixor)),
+            131 => Ok((input, Instruction::Lxor)),
+            132 => {
+                let (input, index) = be_u8(input)?;
+                let (input, value) = be_i8(input)?;
+                Ok((input, Instruction::Iinc { index, value }))
+            }
+            133 => Ok((input, Instruction::I2l)),
+            134 => Ok((input, Instruction::I2f)),
+            135 => Ok((input, Instruction::I2d)),
+            136 => Ok((input, Instruction::L2i)),
+            137 => Ok((input, Instruction::L2f)),
+            138 => Ok((input, Instruction::L2d)),
+            139 => Ok((input, Instruction::F2i)),
+            140 => Ok((input, Instruction::F2l)),
+            141 => Ok((input, Instruction::F2d)),
+            142 => Ok((input, Instruction::D2i)),
+            143 => Ok((input, Instruction::D2l)),
+            144 => Ok((input, Instruction::D2f)),
+            145 => Ok((input, Instruction::I2b)),
+            146 => Ok((input, Instruction::I2c)),
+            147 => Ok((input, Instruction::I2s)),
+            148 => Ok((input, Instruction::Lcmp)),
+            149 => Ok((input, Instruction::Fcmpl)),
+            150 => Ok((input, Instruction::Fcmpg)),
+            151 => Ok((input, Instruction::Dcmpl)),
+            152 => Ok((input, Instruction::Dcmpg)),
+            153 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Ifeq { offset }))
+            }
+            154 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Ifne { offset }))
+            }
+            155 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Iflt { offset }))
+            }
+            156 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Ifge { offset }))
+            }
+            157 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Ifgt { offset }))
+            }
+            158 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Ifle { offset }))
+            }
+            159 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfIcmpeq { offset }))
+            }
+            160 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfIcmpne { offset }))
+            }
+            161 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfIcmplt { offset }))
+            }
+            162 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfIcmpge { offset }))
+            }
+            163 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfIcmpgt { offset }))
+            }
+            164 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfIcmple { offset }))
+            }
+            165 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfAcmpeq { offset }))
+            }
+            166 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::IfAcmpne { offset }))
+            }
+            167 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Goto { offset }))
+            }
+            168 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Jsr { offset }))
+            }
+            169 => {
+                let (input, index) = be_u8(input)?;
+                Ok((input, Instruction::Ret { index }))
+            }
+            170 => {
+                // Zero to three padding bytes bring the cursor to a 4-byte boundary
+                // relative to the start of the code array, then the fixed operands follow.
+                let pad = (4 - (offset + 1) % 4) % 4;
+                let (input, _) = take(pad)(input)?;
+                let (input, default) = nom::number::complete::be_i32(input)?;
+                let (input, low) = nom::number::complete::be_i32(input)?;
+                let (input, high) = nom::number::complete::be_i32(input)?;
+                let (input, jump_offsets) =
+                    nom::multi::count(be_i32, (high - low + 1) as usize)(input)?;
+                Ok((
+                    input,
+                    Instruction::Tableswitch {
+                        default,
+                        low,
+                        high,
+                        jump_offsets: jump_offsets.into_boxed_slice(),
+                    },
+                ))
+            }
+            171 => {
+                let pad = (4 - (offset + 1) % 4) % 4;
+                let (input, _) = take(pad)(input)?;
+                let (input, default) = nom::number::complete::be_i32(input)?;
+                let (input, npairs) = nom::number::complete::be_i32(input)?;
+                let (input, pairs) = nom::multi::count(
+                    nom::sequence::pair(be_i32, be_i32),
+                    npairs as usize,
+                )(input)?;
+                Ok((
+                    input,
+                    Instruction::Lookupswitch {
+                        default,
+                        pairs: pairs.into_boxed_slice(),
+                    },
+                ))
+            }
+            172 => Ok((input, Instruction::Ireturn)),
+            173 => Ok((input, Instruction::Lreturn)),
+            174 => Ok((input, Instruction::Freturn)),
+            175 => Ok((input, Instruction::Dreturn)),
+            176 => Ok((input, Instruction::Areturn)),
             177 => Ok((input, Instruction::Return)),
+            178 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Getstatic { index }))
+            }
+            179 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Putstatic { index }))
+            }
+            180 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Getfield { index }))
+            }
+            181 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Putfield { index }))
+            }
+            182 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Invokevirtual { index }))
+            }
             183 => {
-                let (input, indexbyte1) = nom::number::complete::u8(input)?;
-                let (input, indexbyte2) = nom::number::complete::u8(input)?;
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Invokespecial { index }))
+            }
+            184 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Invokestatic { index }))
+            }
+            185 => {
+                let (input, index) = be_u16(input)?;
+                let (input, count) = be_u8(input)?;
+                let (input, _) = be_u8(input)?; // reserved, always zero
+                Ok((input, Instruction::Invokeinterface { index, count }))
+            }
+            186 => {
+                let (input, index) = be_u16(input)?;
+                let (input, _) = be_u16(input)?; // reserved, always zero
+                Ok((input, Instruction::Invokedynamic { index }))
+            }
+            187 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::New { index }))
+            }
+            188 => {
+                let (input, atype) = be_u8(input)?;
+                Ok((input, Instruction::Newarray { atype }))
+            }
+            189 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Anewarray { index }))
+            }
+            190 => Ok((input, Instruction::Arraylength)),
+            191 => Ok((input, Instruction::Athrow)),
+            192 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Checkcast { index }))
+            }
+            193 => {
+                let (input, index) = be_u16(input)?;
+                Ok((input, Instruction::Instanceof { index }))
+            }
+            194 => Ok((input, Instruction::Monitorenter)),
+            195 => Ok((input, Instruction::Monitorexit)),
+            196 => {
+                let (input, modified_opcode) = be_u8(input)?;
+                match modified_opcode {
+                    21 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Iload { index })))
+                    }
+                    22 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Lload { index })))
+                    }
+                    23 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Fload { index })))
+                    }
+                    24 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Dload { index })))
+                    }
+                    25 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Aload { index })))
+                    }
+                    54 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Istore { index })))
+                    }
+                    55 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Lstore { index })))
+                    }
+                    56 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Fstore { index })))
+                    }
+                    57 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Dstore { index })))
+                    }
+                    58 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Astore { index })))
+                    }
+                    169 => {
+                        let (input, index) = be_u16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Ret { index })))
+                    }
+                    132 => {
+                        let (input, index) = be_u16(input)?;
+                        let (input, value) = be_i16(input)?;
+                        Ok((input, Instruction::Wide(Wide::Iinc { index, value })))
+                    }
+                    _ => Err(nom::Err::Failure(ClassFileError::UnknownOpcode(
+                        modified_opcode,
+                    ))),
+                }
+            }
+            197 => {
+                let (input, index) = be_u16(input)?;
+                let (input, dimensions) = be_u8(input)?;
                 Ok((
                     input,
-                    Instruction::Invokespecial {
-                        indexbyte1,
-                        indexbyte2,
-                    },
+                    Instruction::Multianewarray { index, dimensions },
                 ))
             }
-            _ => panic!("unknown command"),
+            198 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Ifnull { offset }))
+            }
+            199 => {
+                let (input, offset) = be_i16(input)?;
+                Ok((input, Instruction::Ifnonnull { offset }))
+            }
+            200 => {
+                let (input, offset) = be_i32(input)?;
+                Ok((input, Instruction::GotoW { offset }))
+            }
+            201 => {
+                let (input, offset) = be_i32(input)?;
+                Ok((input, Instruction::JsrW { offset }))
+            }
+            _ => Err(nom::Err::Failure(ClassFileError::UnknownOpcode(opcode))),
         }
     }
+
+    /// `offset` is the position of the opcode byte within the method's `code` array,
+    /// needed to compute the padding for `tableswitch`/`lookupswitch`.
+    pub fn write(&self, offset: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            Instruction::Nop => out.push(0),
+            Instruction::AconstNull => out.push(1),
+            Instruction::IconstM1 => out.push(2),
+            Instruction::Iconst0 => out.push(3),
+            Instruction::Iconst1 => out.push(4),
+            Instruction::Iconst2 => out.push(5),
+            Instruction::Iconst3 => out.push(6),
+            Instruction::Iconst4 => out.push(7),
+            Instruction::Iconst5 => out.push(8),
+            Instruction::Lconst0 => out.push(9),
+            Instruction::Lconst1 => out.push(10),
+            Instruction::Fconst0 => out.push(11),
+            Instruction::Fconst1 => out.push(12),
+            Instruction::Fconst2 => out.push(13),
+            Instruction::Dconst0 => out.push(14),
+            Instruction::Dconst1 => out.push(15),
+            Instruction::Bipush { value } => {
+                out.push(16);
+                out.extend(value.to_be_bytes());
+            }
+            Instruction::Sipush { value } => {
+                out.push(17);
+                out.extend(value.to_be_bytes());
+            }
+            Instruction::Ldc { index } => {
+                out.push(18);
+                out.push(*index);
+            }
+            Instruction::LdcW { index } => {
+                out.push(19);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Ldc2W { index } => {
+                out.push(20);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Iload { index } => {
+                out.push(21);
+                out.push(*index);
+            }
+            Instruction::Lload { index } => {
+                out.push(22);
+                out.push(*index);
+            }
+            Instruction::Fload { index } => {
+                out.push(23);
+                out.push(*index);
+            }
+            Instruction::Dload { index } => {
+                out.push(24);
+                out.push(*index);
+            }
+            Instruction::Aload { index } => {
+                out.push(25);
+                out.push(*index);
+            }
+            Instruction::Iload0 => out.push(26),
+            Instruction::Iload1 => out.push(27),
+            Instruction::Iload2 => out.push(28),
+            Instruction::Iload3 => out.push(29),
+            Instruction::Lload0 => out.push(30),
+            Instruction::Lload1 => out.push(31),
+            Instruction::Lload2 => out.push(32),
+            Instruction::Lload3 => out.push(33),
+            Instruction::Fload0 => out.push(34),
+            Instruction::Fload1 => out.push(35),
+            Instruction::Fload2 => out.push(36),
+            Instruction::Fload3 => out.push(37),
+            Instruction::Dload0 => out.push(38),
+            Instruction::Dload1 => out.push(39),
+            Instruction::Dload2 => out.push(40),
+            Instruction::Dload3 => out.push(41),
+            Instruction::Aload0 => out.push(42),
+            Instruction::Aload1 => out.push(43),
+            Instruction::Aload2 => out.push(44),
+            Instruction::Aload3 => out.push(45),
+            Instruction::Iaload => out.push(46),
+            Instruction::Laload => out.push(47),
+            Instruction::Faload => out.push(48),
+            Instruction::Daload => out.push(49),
+            Instruction::Aaload => out.push(50),
+            Instruction::Baload => out.push(51),
+            Instruction::Caload => out.push(52),
+            Instruction::Saload => out.push(53),
+            Instruction::Istore { index } => {
+                out.push(54);
+                out.push(*index);
+            }
+            Instruction::Lstore { index } => {
+                out.push(55);
+                out.push(*index);
+            }
+            Instruction::Fstore { index } => {
+                out.push(56);
+                out.push(*index);
+            }
+            Instruction::Dstore { index } => {
+                out.push(57);
+                out.push(*index);
+            }
+            Instruction::Astore { index } => {
+                out.push(58);
+                out.push(*index);
+            }
+            Instruction::Istore0 => out.push(59),
+            Instruction::Istore1 => out.push(60),
+            Instruction::Istore2 => out.push(61),
+            Instruction::Istore3 => out.push(62),
+            Instruction::Lstore0 => out.push(63),
+            Instruction::Lstore1 => out.push(64),
+            Instruction::Lstore2 => out.push(65),
+            Instruction::Lstore3 => out.push(66),
+            Instruction::Fstore0 => out.push(67),
+            Instruction::Fstore1 => out.push(68),
+            Instruction::Fstore2 => out.push(69),
+            Instruction::Fstore3 => out.push(70),
+            Instruction::Dstore0 => out.push(71),
+            Instruction::Dstore1 => out.push(72),
+            Instruction::Dstore2 => out.push(73),
+            Instruction::Dstore3 => out.push(74),
+            Instruction::Astore0 => out.push(75),
+            Instruction::Astore1 => out.push(76),
+            Instruction::Astore2 => out.push(77),
+            Instruction::Astore3 => out.push(78),
+            Instruction::Iastore => out.push(79),
+            Instruction::Lastore => out.push(80),
+            Instruction::Fastore => out.push(81),
+            Instruction::Dastore => out.push(82),
+            Instruction::Aastore => out.push(83),
+            Instruction::Bastore => out.push(84),
+            Instruction::Castore => out.push(85),
+            Instruction::Sastore => out.push(86),
+            Instruction::Pop => out.push(87),
+            Instruction::Pop2 => out.push(88),
+            Instruction::Dup => out.push(89),
+            Instruction::DupX1 => out.push(90),
+            Instruction::DupX2 => out.push(91),
+            Instruction::Dup2 => out.push(92),
+            Instruction::Dup2X1 => out.push(93),
+            Instruction::Dup2X2 => out.push(94),
+            Instruction::Swap => out.push(95),
+            Instruction::Iadd => out.push(96),
+            Instruction::Ladd => out.push(97),
+            Instruction::Fadd => out.push(98),
+            Instruction::Dadd => out.push(99),
+            Instruction::Isub => out.push(100),
+            Instruction::Lsub => out.push(101),
+            Instruction::Fsub => out.push(102),
+            Instruction::Dsub => out.push(103),
+            Instruction::Imul => out.push(104),
+            Instruction::Lmul => out.push(105),
+            Instruction::Fmul => out.push(106),
+            Instruction::Dmul => out.push(107),
+            Instruction::Idiv => out.push(108),
+            Instruction::Ldiv => out.push(109),
+            Instruction::Fdiv => out.push(110),
+            Instruction::Ddiv => out.push(111),
+            Instruction::Irem => out.push(112),
+            Instruction::Lrem => out.push(113),
+            Instruction::Frem => out.push(114),
+            Instruction::Drem => out.push(115),
+            Instruction::Ineg => out.push(116),
+            Instruction::Lneg => out.push(117),
+            Instruction::Fneg => out.push(118),
+            Instruction::Dneg => out.push(119),
+            Instruction::Ishl => out.push(120),
+            Instruction::Lshl => out.push(121),
+            Instruction::Ishr => out.push(122),
+            Instruction::Lshr => out.push(123),
+            Instruction::Iushr => out.push(124),
+            Instruction::Lushr => out.push(125),
+            Instruction::Iand => out.push(126),
+            Instruction::Land => out.push(127),
+            Instruction::Ior => out.push(128),
+            Instruction::Lor => out.push(129),
+            Instruction::Ixor => out.push(130),
+            Instruction::Lxor => out.push(131),
+            Instruction::Iinc { index, value } => {
+                out.push(132);
+                out.push(*index);
+                out.extend(value.to_be_bytes());
+            }
+            Instruction::I2l => out.push(133),
+            Instruction::I2f => out.push(134),
+            Instruction::I2d => out.push(135),
+            Instruction::L2i => out.push(136),
+            Instruction::L2f => out.push(137),
+            Instruction::L2d => out.push(138),
+            Instruction::F2i => out.push(139),
+            Instruction::F2l => out.push(140),
+            Instruction::F2d => out.push(141),
+            Instruction::D2i => out.push(142),
+            Instruction::D2l => out.push(143),
+            Instruction::D2f => out.push(144),
+            Instruction::I2b => out.push(145),
+            Instruction::I2c => out.push(146),
+            Instruction::I2s => out.push(147),
+            Instruction::Lcmp => out.push(148),
+            Instruction::Fcmpl => out.push(149),
+            Instruction::Fcmpg => out.push(150),
+            Instruction::Dcmpl => out.push(151),
+            Instruction::Dcmpg => out.push(152),
+            Instruction::Ifeq { offset } => {
+                out.push(153);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Ifne { offset } => {
+                out.push(154);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Iflt { offset } => {
+                out.push(155);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Ifge { offset } => {
+                out.push(156);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Ifgt { offset } => {
+                out.push(157);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Ifle { offset } => {
+                out.push(158);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfIcmpeq { offset } => {
+                out.push(159);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfIcmpne { offset } => {
+                out.push(160);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfIcmplt { offset } => {
+                out.push(161);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfIcmpge { offset } => {
+                out.push(162);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfIcmpgt { offset } => {
+                out.push(163);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfIcmple { offset } => {
+                out.push(164);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfAcmpeq { offset } => {
+                out.push(165);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::IfAcmpne { offset } => {
+                out.push(166);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Goto { offset } => {
+                out.push(167);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Jsr { offset } => {
+                out.push(168);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Ret { index } => {
+                out.push(169);
+                out.push(*index);
+            }
+            Instruction::Tableswitch {
+                default,
+                low,
+                high,
+                jump_offsets,
+            } => {
+                out.push(170);
+                let pad = (4 - (offset + 1) % 4) % 4;
+                out.extend(vec![0u8; pad]);
+                out.extend(default.to_be_bytes());
+                out.extend(low.to_be_bytes());
+                out.extend(high.to_be_bytes());
+                for jump_offset in jump_offsets.iter() {
+                    out.extend(jump_offset.to_be_bytes());
+                }
+            }
+            Instruction::Lookupswitch { default, pairs } => {
+                out.push(171);
+                let pad = (4 - (offset + 1) % 4) % 4;
+                out.extend(vec![0u8; pad]);
+                out.extend(default.to_be_bytes());
+                out.extend((pairs.len() as i32).to_be_bytes());
+                for (match_, pair_offset) in pairs.iter() {
+                    out.extend(match_.to_be_bytes());
+                    out.extend(pair_offset.to_be_bytes());
+                }
+            }
+            Instruction::Ireturn => out.push(172),
+            Instruction::Lreturn => out.push(173),
+            Instruction::Freturn => out.push(174),
+            Instruction::Dreturn => out.push(175),
+            Instruction::Areturn => out.push(176),
+            Instruction::Return => out.push(177),
+            Instruction::Getstatic { index } => {
+                out.push(178);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Putstatic { index } => {
+                out.push(179);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Getfield { index } => {
+                out.push(180);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Putfield { index } => {
+                out.push(181);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Invokevirtual { index } => {
+                out.push(182);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Invokespecial { index } => {
+                out.push(183);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Invokestatic { index } => {
+                out.push(184);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Invokeinterface { index, count } => {
+                out.push(185);
+                out.extend(index.to_be_bytes());
+                out.push(*count);
+                out.push(0);
+            }
+            Instruction::Invokedynamic { index } => {
+                out.push(186);
+                out.extend(index.to_be_bytes());
+                out.extend(0u16.to_be_bytes());
+            }
+            Instruction::New { index } => {
+                out.push(187);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Newarray { atype } => {
+                out.push(188);
+                out.push(*atype);
+            }
+            Instruction::Anewarray { index } => {
+                out.push(189);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Arraylength => out.push(190),
+            Instruction::Athrow => out.push(191),
+            Instruction::Checkcast { index } => {
+                out.push(192);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Instanceof { index } => {
+                out.push(193);
+                out.extend(index.to_be_bytes());
+            }
+            Instruction::Monitorenter => out.push(194),
+            Instruction::Monitorexit => out.push(195),
+            Instruction::Wide(wide) => {
+                out.push(196);
+                match wide {
+                    Wide::Iload { index } => {
+                        out.push(21);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Lload { index } => {
+                        out.push(22);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Fload { index } => {
+                        out.push(23);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Dload { index } => {
+                        out.push(24);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Aload { index } => {
+                        out.push(25);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Istore { index } => {
+                        out.push(54);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Lstore { index } => {
+                        out.push(55);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Fstore { index } => {
+                        out.push(56);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Dstore { index } => {
+                        out.push(57);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Astore { index } => {
+                        out.push(58);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Ret { index } => {
+                        out.push(169);
+                        out.extend(index.to_be_bytes());
+                    }
+                    Wide::Iinc { index, value } => {
+                        out.push(132);
+                        out.extend(index.to_be_bytes());
+                        out.extend(value.to_be_bytes());
+                    }
+                }
+            }
+            Instruction::Multianewarray { index, dimensions } => {
+                out.push(197);
+                out.extend(index.to_be_bytes());
+                out.push(*dimensions);
+            }
+            Instruction::Ifnull { offset } => {
+                out.push(198);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::Ifnonnull { offset } => {
+                out.push(199);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::GotoW { offset } => {
+                out.push(200);
+                out.extend(offset.to_be_bytes());
+            }
+            Instruction::JsrW { offset } => {
+                out.push(201);
+                out.extend(offset.to_be_bytes());
+            }
+        }
+        out
+    }
+
+    /// Computes each instruction's byte offset within `code`, by replaying
+    /// [`Instruction::write`] the same way the `Code` attribute's own serializer
+    /// computes its `code_length`.
+    pub fn offsets(code: &[Instruction]) -> Vec<usize> {
+        let mut offsets = Vec::with_capacity(code.len());
+        let mut pc = 0;
+        for instruction in code {
+            offsets.push(pc);
+            pc += instruction.write(pc).len();
+        }
+        offsets
+    }
+
+    /// Pairs each instruction in `code` with its byte offset, as consumers that
+    /// inspect control flow (branch targets, exception ranges) want: `(pc, instruction)`.
+    pub fn with_offsets(code: &[Instruction]) -> Vec<(u32, &Instruction)> {
+        Instruction::offsets(code)
+            .into_iter()
+            .map(|offset| offset as u32)
+            .zip(code.iter())
+            .collect()
+    }
 }
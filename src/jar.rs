@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Seek};
+
+use crate::classfile::ClassFile;
+
+/// Errors that can occur while loading every `.class` entry out of a JAR archive.
+#[derive(Debug)]
+pub enum JarError {
+    Zip(zip::result::ZipError),
+    Io(std::io::Error),
+    ClassFile {
+        entry: String,
+        source: Box<dyn std::error::Error>,
+    },
+}
+
+impl std::fmt::Display for JarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JarError::Zip(err) => write!(f, "failed to read JAR archive: {err}"),
+            JarError::Io(err) => write!(f, "I/O error reading JAR entry: {err}"),
+            JarError::ClassFile { entry, source } => {
+                write!(f, "failed to parse {entry}: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JarError {}
+
+impl From<zip::result::ZipError> for JarError {
+    fn from(err: zip::result::ZipError) -> Self {
+        JarError::Zip(err)
+    }
+}
+
+impl From<std::io::Error> for JarError {
+    fn from(err: std::io::Error) -> Self {
+        JarError::Io(err)
+    }
+}
+
+/// Parses every `.class` entry in a JAR (zip) archive, keyed by its internal entry
+/// name (e.g. `java/lang/Object.class`).
+pub fn read_all_from_jar<R: Read + Seek>(
+    reader: R,
+) -> Result<BTreeMap<String, ClassFile>, JarError> {
+    let mut archive = zip::ZipArchive::new(reader)?;
+    let mut classes = BTreeMap::new();
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if !entry.name().ends_with(".class") {
+            continue;
+        }
+        let name = entry.name().to_string();
+
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+
+        let class = match ClassFile::read(&bytes) {
+            Ok((_, class)) => class,
+            Err(err) => {
+                return Err(JarError::ClassFile {
+                    entry: name,
+                    source: err.to_owned().into(),
+                })
+            }
+        };
+
+        classes.insert(name, class);
+    }
+
+    Ok(classes)
+}
@@ -1,16 +1,33 @@
+use std::path::Path;
+
 use cgr::classfile::ClassFile;
+use cgr::disassembler::disassemble;
+use cgr::jar::read_all_from_jar;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let file = std::env::args()
+    let path = std::env::args()
         .nth(1)
-        .expect("First argument is input class file");
-    let file = std::fs::read(file)?;
+        .expect("First argument is input class or jar file");
+
+    if Path::new(&path).extension().is_some_and(|ext| ext == "jar") {
+        let file = std::fs::File::open(&path)?;
+        let classes = read_all_from_jar(file)?;
+        for (entry, class) in &classes {
+            let this_class = class.this_class_name().unwrap_or("<invalid>");
+            println!("{entry}: {this_class}");
+            println!("{}", disassemble(class)?);
+        }
+        println!("{} classes found", classes.len());
+        return Ok(());
+    }
+
+    let file = std::fs::read(path)?;
     let file = match ClassFile::read(&file) {
         Ok((_, file)) => file,
         Err(err) => return Err(err.to_owned().into()),
     };
 
-    println!("{file:#?}");
+    println!("{}", disassemble(&file)?);
 
     Ok(())
 }
@@ -1,29 +1,1153 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
-struct Vm {
-    call_stack: CallStack,
-    pc: usize,
+use crate::classfile::ClassFile;
+use crate::error::ClassFileError;
+use crate::instruction::{Instruction, Wide};
+
+/// Errors that can occur while interpreting a method's bytecode.
+#[derive(Debug, Clone)]
+pub enum VmError {
+    MethodNotFound { name: String, descriptor: String },
+    StackUnderflow,
+    InvalidLocalIndex(usize),
+    InvalidJumpTarget(usize),
+    NullReference,
+    /// A call or field access that targets a class other than the one being
+    /// interpreted. The interpreter only resolves members of its own class.
+    UnsupportedExternalCall {
+        class_name: String,
+        member_name: String,
+    },
+    UnsupportedInstruction(String),
+    ConstantPool(ClassFileError),
 }
 
-struct CallStack {
-    frames: VecDeque<Frame>,
+impl std::fmt::Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::MethodNotFound { name, descriptor } => {
+                write!(f, "no method named {name}{descriptor}")
+            }
+            VmError::StackUnderflow => write!(f, "operand stack underflow"),
+            VmError::InvalidLocalIndex(index) => write!(f, "invalid local variable index {index}"),
+            VmError::InvalidJumpTarget(offset) => {
+                write!(f, "jump target {offset} is not an instruction boundary")
+            }
+            VmError::NullReference => write!(f, "unexpected null reference"),
+            VmError::UnsupportedExternalCall {
+                class_name,
+                member_name,
+            } => write!(
+                f,
+                "unsupported reference to {member_name} on external class {class_name}"
+            ),
+            VmError::UnsupportedInstruction(message) => {
+                write!(f, "unsupported instruction: {message}")
+            }
+            VmError::ConstantPool(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for VmError {}
+
+impl From<ClassFileError> for VmError {
+    fn from(err: ClassFileError) -> Self {
+        VmError::ConstantPool(err)
+    }
 }
 
-enum Operand {
+/// A value living on an operand stack, in a local variable slot, or in a field.
+/// Categories are collapsed the way the JVM's own stack map does: the interpreter
+/// trusts the bytecode's declared types rather than re-deriving them.
+#[derive(Debug, Clone, Copy)]
+pub enum Operand {
     Uninitialized,
-    Byte(i8),
-    Short(i16),
     Int(i32),
     Long(i64),
-    Char(u16),
     Float(f32),
     Double(f64),
-    Bool(bool),
     ReturnAddress(usize),
-    Object {},
+    /// `None` is `null`; `Some(handle)` indexes into the [`Vm`]'s heap.
+    Object(Option<usize>),
 }
 
-struct Frame {
+/// An arena of heap-allocated objects, each a bag of named fields. There is no
+/// notion of a class shape here: fields are created on first `putfield` and
+/// read back by name, which is enough for the single-class scope this
+/// interpreter supports.
+#[derive(Default)]
+struct Heap {
+    objects: Vec<HashMap<String, Operand>>,
+}
+
+impl Heap {
+    fn allocate(&mut self) -> usize {
+        self.objects.push(HashMap::new());
+        self.objects.len() - 1
+    }
+
+    fn fields(&self, handle: usize) -> Result<&HashMap<String, Operand>, VmError> {
+        self.objects.get(handle).ok_or(VmError::NullReference)
+    }
+
+    fn fields_mut(&mut self, handle: usize) -> Result<&mut HashMap<String, Operand>, VmError> {
+        self.objects.get_mut(handle).ok_or(VmError::NullReference)
+    }
+}
+
+/// A single method activation: the decoded instruction stream it's executing,
+/// per-instruction byte offsets (for resolving branch targets), and its
+/// locals and operand stack.
+struct Frame<'a> {
+    code: &'a [Instruction],
+    offsets: Vec<usize>,
+    offset_to_index: HashMap<usize, usize>,
+    pc: usize,
     locals: Vec<Operand>,
     operands: VecDeque<Operand>,
 }
+
+impl<'a> Frame<'a> {
+    fn new(code: &'a [Instruction], locals: Vec<Operand>) -> Self {
+        let offsets = Instruction::offsets(code);
+        let offset_to_index = offsets
+            .iter()
+            .enumerate()
+            .map(|(index, &offset)| (offset, index))
+            .collect();
+        Self {
+            code,
+            offsets,
+            offset_to_index,
+            pc: 0,
+            locals,
+            operands: VecDeque::new(),
+        }
+    }
+}
+
+struct CallStack<'a> {
+    frames: VecDeque<Frame<'a>>,
+}
+
+/// A minimal fetch-decode-execute interpreter for a single `ClassFile` with no
+/// superclass support: `invokespecial`/`invokestatic`/`invokevirtual`, `new`,
+/// `getfield`/`putfield`, and `getstatic`/`putstatic` only resolve members of
+/// the class being interpreted. `java/lang/Object.<init>` is special-cased as
+/// a no-op so ordinary constructors still run. Anything else that crosses a
+/// class boundary (including every `java.lang`/`java.io` call a real `main`
+/// would make) surfaces as [`VmError::UnsupportedExternalCall`].
+pub struct Vm<'a> {
+    class: &'a ClassFile,
+    heap: Heap,
+    statics: HashMap<String, Operand>,
+    call_stack: CallStack<'a>,
+}
+
+enum InvokeKind {
+    Static,
+    Special,
+    Virtual,
+}
+
+impl<'a> Vm<'a> {
+    pub fn new(class: &'a ClassFile) -> Self {
+        Self {
+            class,
+            heap: Heap::default(),
+            statics: HashMap::new(),
+            call_stack: CallStack {
+                frames: VecDeque::new(),
+            },
+        }
+    }
+
+    /// Calls a method by name and descriptor with `args` as its initial locals
+    /// (`this` first, for instance methods), running it to completion.
+    pub fn call(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        args: Vec<Operand>,
+    ) -> Result<Option<Operand>, VmError> {
+        let frame = self.make_frame(name, descriptor, args)?;
+        self.call_stack.frames.push_back(frame);
+        self.run()
+    }
+
+    fn make_frame(
+        &self,
+        name: &str,
+        descriptor: &str,
+        mut locals: Vec<Operand>,
+    ) -> Result<Frame<'a>, VmError> {
+        let method = self
+            .class
+            .find_method(name, descriptor)?
+            .ok_or_else(|| VmError::MethodNotFound {
+                name: name.to_string(),
+                descriptor: descriptor.to_string(),
+            })?;
+        locals.resize(method.max_locals as usize, Operand::Uninitialized);
+        Ok(Frame::new(method.code, locals))
+    }
+
+    fn current_frame_mut(&mut self) -> &mut Frame<'a> {
+        self.call_stack
+            .frames
+            .back_mut()
+            .expect("a frame is always on the call stack while running")
+    }
+
+    fn push(&mut self, value: Operand) {
+        self.current_frame_mut().operands.push_back(value);
+    }
+
+    fn pop(&mut self) -> Result<Operand, VmError> {
+        self.current_frame_mut()
+            .operands
+            .pop_back()
+            .ok_or(VmError::StackUnderflow)
+    }
+
+    fn pop_int(&mut self) -> Result<i32, VmError> {
+        match self.pop()? {
+            Operand::Int(value) => Ok(value),
+            other => Err(VmError::UnsupportedInstruction(format!(
+                "expected an int operand, found {other:?}"
+            ))),
+        }
+    }
+
+    fn pop_long(&mut self) -> Result<i64, VmError> {
+        match self.pop()? {
+            Operand::Long(value) => Ok(value),
+            other => Err(VmError::UnsupportedInstruction(format!(
+                "expected a long operand, found {other:?}"
+            ))),
+        }
+    }
+
+    fn pop_float(&mut self) -> Result<f32, VmError> {
+        match self.pop()? {
+            Operand::Float(value) => Ok(value),
+            other => Err(VmError::UnsupportedInstruction(format!(
+                "expected a float operand, found {other:?}"
+            ))),
+        }
+    }
+
+    fn pop_double(&mut self) -> Result<f64, VmError> {
+        match self.pop()? {
+            Operand::Double(value) => Ok(value),
+            other => Err(VmError::UnsupportedInstruction(format!(
+                "expected a double operand, found {other:?}"
+            ))),
+        }
+    }
+
+    fn local(&self, index: usize) -> Result<Operand, VmError> {
+        self.call_stack
+            .frames
+            .back()
+            .expect("a frame is always on the call stack while running")
+            .locals
+            .get(index)
+            .copied()
+            .ok_or(VmError::InvalidLocalIndex(index))
+    }
+
+    fn set_local(&mut self, index: usize, value: Operand) -> Result<(), VmError> {
+        let slot = self
+            .current_frame_mut()
+            .locals
+            .get_mut(index)
+            .ok_or(VmError::InvalidLocalIndex(index))?;
+        *slot = value;
+        Ok(())
+    }
+
+    /// Jumps to the instruction at `byte_offset + relative`, where `byte_offset`
+    /// is the position of the branch instruction's own opcode in the method's
+    /// `code` array.
+    fn jump(&mut self, byte_offset: usize, relative: isize) -> Result<(), VmError> {
+        let target = (byte_offset as isize + relative) as usize;
+        let frame = self.current_frame_mut();
+        let index = *frame
+            .offset_to_index
+            .get(&target)
+            .ok_or(VmError::InvalidJumpTarget(target))?;
+        frame.pc = index;
+        Ok(())
+    }
+
+    fn this_class_name(&self) -> Result<&str, VmError> {
+        Ok(self.class.this_class_name()?)
+    }
+
+    fn exec_new(&mut self, index: u16) -> Result<(), VmError> {
+        let class_name = self.class.constant_pool().class_name(index)?;
+        if class_name != self.this_class_name()? {
+            return Err(VmError::UnsupportedExternalCall {
+                class_name: class_name.to_string(),
+                member_name: "<new>".to_string(),
+            });
+        }
+        let handle = self.heap.allocate();
+        self.push(Operand::Object(Some(handle)));
+        Ok(())
+    }
+
+    fn exec_getfield(&mut self, index: u16) -> Result<(), VmError> {
+        let (class_name, field_name, _) = self.class.constant_pool().field_ref(index)?;
+        if class_name != self.this_class_name()? {
+            return Err(VmError::UnsupportedExternalCall {
+                class_name: class_name.to_string(),
+                member_name: field_name.to_string(),
+            });
+        }
+        let handle = match self.pop()? {
+            Operand::Object(Some(handle)) => handle,
+            Operand::Object(None) => return Err(VmError::NullReference),
+            other => {
+                return Err(VmError::UnsupportedInstruction(format!(
+                    "getfield on non-reference operand {other:?}"
+                )))
+            }
+        };
+        let value = self
+            .heap
+            .fields(handle)?
+            .get(field_name)
+            .copied()
+            .unwrap_or(Operand::Uninitialized);
+        self.push(value);
+        Ok(())
+    }
+
+    fn exec_putfield(&mut self, index: u16) -> Result<(), VmError> {
+        let (class_name, field_name, _) = self.class.constant_pool().field_ref(index)?;
+        if class_name != self.this_class_name()? {
+            return Err(VmError::UnsupportedExternalCall {
+                class_name: class_name.to_string(),
+                member_name: field_name.to_string(),
+            });
+        }
+        let value = self.pop()?;
+        let handle = match self.pop()? {
+            Operand::Object(Some(handle)) => handle,
+            Operand::Object(None) => return Err(VmError::NullReference),
+            other => {
+                return Err(VmError::UnsupportedInstruction(format!(
+                    "putfield on non-reference operand {other:?}"
+                )))
+            }
+        };
+        self.heap
+            .fields_mut(handle)?
+            .insert(field_name.to_string(), value);
+        Ok(())
+    }
+
+    fn exec_getstatic(&mut self, index: u16) -> Result<(), VmError> {
+        let (class_name, field_name, _) = self.class.constant_pool().field_ref(index)?;
+        if class_name != self.this_class_name()? {
+            return Err(VmError::UnsupportedExternalCall {
+                class_name: class_name.to_string(),
+                member_name: field_name.to_string(),
+            });
+        }
+        let value = self
+            .statics
+            .get(field_name)
+            .copied()
+            .unwrap_or(Operand::Uninitialized);
+        self.push(value);
+        Ok(())
+    }
+
+    fn exec_putstatic(&mut self, index: u16) -> Result<(), VmError> {
+        let (class_name, field_name, _) = self.class.constant_pool().field_ref(index)?;
+        if class_name != self.this_class_name()? {
+            return Err(VmError::UnsupportedExternalCall {
+                class_name: class_name.to_string(),
+                member_name: field_name.to_string(),
+            });
+        }
+        let value = self.pop()?;
+        self.statics.insert(field_name.to_string(), value);
+        Ok(())
+    }
+
+    fn exec_instanceof(&mut self, index: u16) -> Result<(), VmError> {
+        let class_name = self.class.constant_pool().class_name(index)?;
+        if class_name != self.this_class_name()? {
+            return Err(VmError::UnsupportedExternalCall {
+                class_name: class_name.to_string(),
+                member_name: "<instanceof>".to_string(),
+            });
+        }
+        let result = match self.pop()? {
+            Operand::Object(Some(_)) => 1,
+            _ => 0,
+        };
+        self.push(Operand::Int(result));
+        Ok(())
+    }
+
+    fn exec_ldc(&mut self, index: u16) -> Result<(), VmError> {
+        let pool = self.class.constant_pool();
+        if let Ok(value) = pool.integer(index) {
+            self.push(Operand::Int(value));
+            return Ok(());
+        }
+        if let Ok(value) = pool.float(index) {
+            self.push(Operand::Float(value));
+            return Ok(());
+        }
+        Err(VmError::UnsupportedInstruction(format!(
+            "ldc of unsupported constant at index {index}"
+        )))
+    }
+
+    fn exec_ldc2w(&mut self, index: u16) -> Result<(), VmError> {
+        let pool = self.class.constant_pool();
+        if let Ok(value) = pool.long(index) {
+            self.push(Operand::Long(value));
+            return Ok(());
+        }
+        if let Ok(value) = pool.double(index) {
+            self.push(Operand::Double(value));
+            return Ok(());
+        }
+        Err(VmError::UnsupportedInstruction(format!(
+            "ldc2_w of unsupported constant at index {index}"
+        )))
+    }
+
+    /// Resolves `invokespecial`/`invokestatic`/`invokevirtual` to a method of
+    /// the class being interpreted, pops its arguments (and receiver, for
+    /// non-static calls) off the caller's operand stack, and pushes a new
+    /// frame for it. `java/lang/Object.<init>` is special-cased as a no-op.
+    fn invoke(&mut self, index: u16, kind: InvokeKind) -> Result<(), VmError> {
+        let (class_name, method_name, descriptor) = self.class.constant_pool().method_ref(index)?;
+
+        if class_name != self.this_class_name()? {
+            if matches!(kind, InvokeKind::Special) && method_name == "<init>" {
+                self.pop()?; // objectref; java/lang/Object.<init> takes no arguments
+                return Ok(());
+            }
+            return Err(VmError::UnsupportedExternalCall {
+                class_name: class_name.to_string(),
+                member_name: method_name.to_string(),
+            });
+        }
+
+        let param_count = crate::descriptor::parse_method_descriptor(descriptor)?
+            .parameters
+            .len();
+        let mut args = Vec::with_capacity(param_count);
+        for _ in 0..param_count {
+            args.push(self.pop()?);
+        }
+        args.reverse();
+        if !matches!(kind, InvokeKind::Static) {
+            args.insert(0, self.pop()?); // objectref
+        }
+
+        let frame = self.make_frame(method_name, descriptor, to_local_slots(args))?;
+        self.call_stack.frames.push_back(frame);
+        Ok(())
+    }
+
+    fn run(&mut self) -> Result<Option<Operand>, VmError> {
+        loop {
+            let (code, pc, byte_offset) = {
+                let frame = self
+                    .call_stack
+                    .frames
+                    .back()
+                    .expect("a frame is always on the call stack while running");
+                (frame.code, frame.pc, frame.offsets[frame.pc])
+            };
+            let instruction = &code[pc];
+            self.current_frame_mut().pc += 1;
+
+            match instruction {
+                Instruction::Nop => {}
+                Instruction::AconstNull => self.push(Operand::Object(None)),
+                Instruction::IconstM1 => self.push(Operand::Int(-1)),
+                Instruction::Iconst0 => self.push(Operand::Int(0)),
+                Instruction::Iconst1 => self.push(Operand::Int(1)),
+                Instruction::Iconst2 => self.push(Operand::Int(2)),
+                Instruction::Iconst3 => self.push(Operand::Int(3)),
+                Instruction::Iconst4 => self.push(Operand::Int(4)),
+                Instruction::Iconst5 => self.push(Operand::Int(5)),
+                Instruction::Lconst0 => self.push(Operand::Long(0)),
+                Instruction::Lconst1 => self.push(Operand::Long(1)),
+                Instruction::Fconst0 => self.push(Operand::Float(0.0)),
+                Instruction::Fconst1 => self.push(Operand::Float(1.0)),
+                Instruction::Fconst2 => self.push(Operand::Float(2.0)),
+                Instruction::Dconst0 => self.push(Operand::Double(0.0)),
+                Instruction::Dconst1 => self.push(Operand::Double(1.0)),
+                Instruction::Bipush { value } => self.push(Operand::Int(*value as i32)),
+                Instruction::Sipush { value } => self.push(Operand::Int(*value as i32)),
+                Instruction::Ldc { index } => self.exec_ldc(*index as u16)?,
+                Instruction::LdcW { index } => self.exec_ldc(*index)?,
+                Instruction::Ldc2W { index } => self.exec_ldc2w(*index)?,
+
+                Instruction::Iload { index }
+                | Instruction::Lload { index }
+                | Instruction::Fload { index }
+                | Instruction::Dload { index }
+                | Instruction::Aload { index } => {
+                    let value = self.local(*index as usize)?;
+                    self.push(value);
+                }
+                Instruction::Iload0
+                | Instruction::Lload0
+                | Instruction::Fload0
+                | Instruction::Dload0
+                | Instruction::Aload0 => {
+                    let value = self.local(0)?;
+                    self.push(value);
+                }
+                Instruction::Iload1
+                | Instruction::Lload1
+                | Instruction::Fload1
+                | Instruction::Dload1
+                | Instruction::Aload1 => {
+                    let value = self.local(1)?;
+                    self.push(value);
+                }
+                Instruction::Iload2
+                | Instruction::Lload2
+                | Instruction::Fload2
+                | Instruction::Dload2
+                | Instruction::Aload2 => {
+                    let value = self.local(2)?;
+                    self.push(value);
+                }
+                Instruction::Iload3
+                | Instruction::Lload3
+                | Instruction::Fload3
+                | Instruction::Dload3
+                | Instruction::Aload3 => {
+                    let value = self.local(3)?;
+                    self.push(value);
+                }
+
+                Instruction::Istore { index }
+                | Instruction::Lstore { index }
+                | Instruction::Fstore { index }
+                | Instruction::Dstore { index }
+                | Instruction::Astore { index } => {
+                    let value = self.pop()?;
+                    self.set_local(*index as usize, value)?;
+                }
+                Instruction::Istore0
+                | Instruction::Lstore0
+                | Instruction::Fstore0
+                | Instruction::Dstore0
+                | Instruction::Astore0 => {
+                    let value = self.pop()?;
+                    self.set_local(0, value)?;
+                }
+                Instruction::Istore1
+                | Instruction::Lstore1
+                | Instruction::Fstore1
+                | Instruction::Dstore1
+                | Instruction::Astore1 => {
+                    let value = self.pop()?;
+                    self.set_local(1, value)?;
+                }
+                Instruction::Istore2
+                | Instruction::Lstore2
+                | Instruction::Fstore2
+                | Instruction::Dstore2
+                | Instruction::Astore2 => {
+                    let value = self.pop()?;
+                    self.set_local(2, value)?;
+                }
+                Instruction::Istore3
+                | Instruction::Lstore3
+                | Instruction::Fstore3
+                | Instruction::Dstore3
+                | Instruction::Astore3 => {
+                    let value = self.pop()?;
+                    self.set_local(3, value)?;
+                }
+
+                Instruction::Pop => {
+                    self.pop()?;
+                }
+                Instruction::Pop2 => {
+                    self.pop()?;
+                    self.pop()?;
+                }
+                Instruction::Dup => {
+                    let value = self.pop()?;
+                    self.push(value);
+                    self.push(value);
+                }
+                Instruction::Dup2 => {
+                    let top = self.pop()?;
+                    let second = self.pop()?;
+                    self.push(second);
+                    self.push(top);
+                    self.push(second);
+                    self.push(top);
+                }
+                Instruction::Swap => {
+                    let top = self.pop()?;
+                    let second = self.pop()?;
+                    self.push(top);
+                    self.push(second);
+                }
+
+                Instruction::Iadd => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a.wrapping_add(b)));
+                }
+                Instruction::Isub => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a.wrapping_sub(b)));
+                }
+                Instruction::Imul => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a.wrapping_mul(b)));
+                }
+                Instruction::Idiv => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if b == 0 {
+                        return Err(VmError::UnsupportedInstruction(
+                            "division by zero".to_string(),
+                        ));
+                    }
+                    self.push(Operand::Int(a.wrapping_div(b)));
+                }
+                Instruction::Irem => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if b == 0 {
+                        return Err(VmError::UnsupportedInstruction(
+                            "division by zero".to_string(),
+                        ));
+                    }
+                    self.push(Operand::Int(a.wrapping_rem(b)));
+                }
+                Instruction::Ineg => {
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a.wrapping_neg()));
+                }
+                Instruction::Iand => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a & b));
+                }
+                Instruction::Ior => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a | b));
+                }
+                Instruction::Ixor => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a ^ b));
+                }
+                Instruction::Ishl => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a.wrapping_shl(b as u32 & 0x1f)));
+                }
+                Instruction::Ishr => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(a.wrapping_shr(b as u32 & 0x1f)));
+                }
+                Instruction::Iushr => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    self.push(Operand::Int(
+                        (a as u32).wrapping_shr(b as u32 & 0x1f) as i32,
+                    ));
+                }
+
+                Instruction::Ladd => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a.wrapping_add(b)));
+                }
+                Instruction::Lsub => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a.wrapping_sub(b)));
+                }
+                Instruction::Lmul => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a.wrapping_mul(b)));
+                }
+                Instruction::Ldiv => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    if b == 0 {
+                        return Err(VmError::UnsupportedInstruction(
+                            "division by zero".to_string(),
+                        ));
+                    }
+                    self.push(Operand::Long(a.wrapping_div(b)));
+                }
+                Instruction::Lrem => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    if b == 0 {
+                        return Err(VmError::UnsupportedInstruction(
+                            "division by zero".to_string(),
+                        ));
+                    }
+                    self.push(Operand::Long(a.wrapping_rem(b)));
+                }
+                Instruction::Lneg => {
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a.wrapping_neg()));
+                }
+                Instruction::Land => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a & b));
+                }
+                Instruction::Lor => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a | b));
+                }
+                Instruction::Lxor => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a ^ b));
+                }
+                Instruction::Lshl => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a.wrapping_shl(b as u32 & 0x3f)));
+                }
+                Instruction::Lshr => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(a.wrapping_shr(b as u32 & 0x3f)));
+                }
+                Instruction::Lushr => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Long(
+                        (a as u64).wrapping_shr(b as u32 & 0x3f) as i64,
+                    ));
+                }
+
+                Instruction::Fadd => {
+                    let b = self.pop_float()?;
+                    let a = self.pop_float()?;
+                    self.push(Operand::Float(a + b));
+                }
+                Instruction::Fsub => {
+                    let b = self.pop_float()?;
+                    let a = self.pop_float()?;
+                    self.push(Operand::Float(a - b));
+                }
+                Instruction::Fmul => {
+                    let b = self.pop_float()?;
+                    let a = self.pop_float()?;
+                    self.push(Operand::Float(a * b));
+                }
+                Instruction::Fdiv => {
+                    let b = self.pop_float()?;
+                    let a = self.pop_float()?;
+                    self.push(Operand::Float(a / b));
+                }
+                Instruction::Frem => {
+                    let b = self.pop_float()?;
+                    let a = self.pop_float()?;
+                    self.push(Operand::Float(a % b));
+                }
+                Instruction::Fneg => {
+                    let a = self.pop_float()?;
+                    self.push(Operand::Float(-a));
+                }
+
+                Instruction::Dadd => {
+                    let b = self.pop_double()?;
+                    let a = self.pop_double()?;
+                    self.push(Operand::Double(a + b));
+                }
+                Instruction::Dsub => {
+                    let b = self.pop_double()?;
+                    let a = self.pop_double()?;
+                    self.push(Operand::Double(a - b));
+                }
+                Instruction::Dmul => {
+                    let b = self.pop_double()?;
+                    let a = self.pop_double()?;
+                    self.push(Operand::Double(a * b));
+                }
+                Instruction::Ddiv => {
+                    let b = self.pop_double()?;
+                    let a = self.pop_double()?;
+                    self.push(Operand::Double(a / b));
+                }
+                Instruction::Drem => {
+                    let b = self.pop_double()?;
+                    let a = self.pop_double()?;
+                    self.push(Operand::Double(a % b));
+                }
+                Instruction::Dneg => {
+                    let a = self.pop_double()?;
+                    self.push(Operand::Double(-a));
+                }
+
+                Instruction::Iinc { index, value } => {
+                    let current = self.local(*index as usize)?;
+                    match current {
+                        Operand::Int(v) => {
+                            self.set_local(
+                                *index as usize,
+                                Operand::Int(v.wrapping_add(*value as i32)),
+                            )?;
+                        }
+                        other => {
+                            return Err(VmError::UnsupportedInstruction(format!(
+                                "iinc on non-int local {other:?}"
+                            )))
+                        }
+                    }
+                }
+
+                Instruction::I2l => {
+                    let v = self.pop_int()?;
+                    self.push(Operand::Long(v as i64));
+                }
+                Instruction::I2f => {
+                    let v = self.pop_int()?;
+                    self.push(Operand::Float(v as f32));
+                }
+                Instruction::I2d => {
+                    let v = self.pop_int()?;
+                    self.push(Operand::Double(v as f64));
+                }
+                Instruction::L2i => {
+                    let v = self.pop_long()?;
+                    self.push(Operand::Int(v as i32));
+                }
+                Instruction::L2f => {
+                    let v = self.pop_long()?;
+                    self.push(Operand::Float(v as f32));
+                }
+                Instruction::L2d => {
+                    let v = self.pop_long()?;
+                    self.push(Operand::Double(v as f64));
+                }
+                Instruction::F2i => {
+                    let v = self.pop_float()?;
+                    self.push(Operand::Int(v as i32));
+                }
+                Instruction::F2l => {
+                    let v = self.pop_float()?;
+                    self.push(Operand::Long(v as i64));
+                }
+                Instruction::F2d => {
+                    let v = self.pop_float()?;
+                    self.push(Operand::Double(v as f64));
+                }
+                Instruction::D2i => {
+                    let v = self.pop_double()?;
+                    self.push(Operand::Int(v as i32));
+                }
+                Instruction::D2l => {
+                    let v = self.pop_double()?;
+                    self.push(Operand::Long(v as i64));
+                }
+                Instruction::D2f => {
+                    let v = self.pop_double()?;
+                    self.push(Operand::Float(v as f32));
+                }
+                Instruction::I2b => {
+                    let v = self.pop_int()?;
+                    self.push(Operand::Int(v as i8 as i32));
+                }
+                Instruction::I2c => {
+                    let v = self.pop_int()?;
+                    self.push(Operand::Int(v as u16 as i32));
+                }
+                Instruction::I2s => {
+                    let v = self.pop_int()?;
+                    self.push(Operand::Int(v as i16 as i32));
+                }
+
+                Instruction::Lcmp => {
+                    let b = self.pop_long()?;
+                    let a = self.pop_long()?;
+                    self.push(Operand::Int(a.cmp(&b) as i32));
+                }
+                Instruction::Fcmpl => {
+                    let b = self.pop_float()?;
+                    let a = self.pop_float()?;
+                    self.push(Operand::Int(float_compare(a, b, -1)));
+                }
+                Instruction::Fcmpg => {
+                    let b = self.pop_float()?;
+                    let a = self.pop_float()?;
+                    self.push(Operand::Int(float_compare(a, b, 1)));
+                }
+                Instruction::Dcmpl => {
+                    let b = self.pop_double()?;
+                    let a = self.pop_double()?;
+                    self.push(Operand::Int(double_compare(a, b, -1)));
+                }
+                Instruction::Dcmpg => {
+                    let b = self.pop_double()?;
+                    let a = self.pop_double()?;
+                    self.push(Operand::Int(double_compare(a, b, 1)));
+                }
+
+                Instruction::Ifeq { offset } => {
+                    if self.pop_int()? == 0 {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Ifne { offset } => {
+                    if self.pop_int()? != 0 {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Iflt { offset } => {
+                    if self.pop_int()? < 0 {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Ifge { offset } => {
+                    if self.pop_int()? >= 0 {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Ifgt { offset } => {
+                    if self.pop_int()? > 0 {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Ifle { offset } => {
+                    if self.pop_int()? <= 0 {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfIcmpeq { offset } => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if a == b {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfIcmpne { offset } => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if a != b {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfIcmplt { offset } => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if a < b {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfIcmpge { offset } => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if a >= b {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfIcmpgt { offset } => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if a > b {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfIcmple { offset } => {
+                    let b = self.pop_int()?;
+                    let a = self.pop_int()?;
+                    if a <= b {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfAcmpeq { offset } => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    if reference_eq(a, b)? {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::IfAcmpne { offset } => {
+                    let b = self.pop()?;
+                    let a = self.pop()?;
+                    if !reference_eq(a, b)? {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Ifnull { offset } => {
+                    if matches!(self.pop()?, Operand::Object(None)) {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Ifnonnull { offset } => {
+                    if matches!(self.pop()?, Operand::Object(Some(_))) {
+                        self.jump(byte_offset, *offset as isize)?;
+                    }
+                }
+                Instruction::Goto { offset } => self.jump(byte_offset, *offset as isize)?,
+                Instruction::GotoW { offset } => self.jump(byte_offset, *offset as isize)?,
+
+                Instruction::Tableswitch {
+                    default,
+                    low,
+                    high,
+                    jump_offsets,
+                } => {
+                    let value = self.pop_int()?;
+                    let relative = if value < *low || value > *high {
+                        *default
+                    } else {
+                        jump_offsets[(value - low) as usize]
+                    };
+                    self.jump(byte_offset, relative as isize)?;
+                }
+                Instruction::Lookupswitch { default, pairs } => {
+                    let value = self.pop_int()?;
+                    let relative = pairs
+                        .iter()
+                        .find(|(match_, _)| *match_ == value)
+                        .map(|(_, target)| *target)
+                        .unwrap_or(*default);
+                    self.jump(byte_offset, relative as isize)?;
+                }
+
+                Instruction::Ireturn
+                | Instruction::Lreturn
+                | Instruction::Freturn
+                | Instruction::Dreturn
+                | Instruction::Areturn => {
+                    let value = self.pop()?;
+                    self.call_stack.frames.pop_back();
+                    if self.call_stack.frames.is_empty() {
+                        return Ok(Some(value));
+                    }
+                    self.push(value);
+                }
+                Instruction::Return => {
+                    self.call_stack.frames.pop_back();
+                    if self.call_stack.frames.is_empty() {
+                        return Ok(None);
+                    }
+                }
+
+                Instruction::Invokestatic { index } => self.invoke(*index, InvokeKind::Static)?,
+                Instruction::Invokespecial { index } => {
+                    self.invoke(*index, InvokeKind::Special)?
+                }
+                Instruction::Invokevirtual { index } => {
+                    self.invoke(*index, InvokeKind::Virtual)?
+                }
+
+                Instruction::New { index } => self.exec_new(*index)?,
+                Instruction::Getfield { index } => self.exec_getfield(*index)?,
+                Instruction::Putfield { index } => self.exec_putfield(*index)?,
+                Instruction::Getstatic { index } => self.exec_getstatic(*index)?,
+                Instruction::Putstatic { index } => self.exec_putstatic(*index)?,
+                Instruction::Checkcast { .. } => {}
+                Instruction::Instanceof { index } => self.exec_instanceof(*index)?,
+
+                Instruction::Wide(wide) => match wide {
+                    Wide::Iload { index }
+                    | Wide::Lload { index }
+                    | Wide::Fload { index }
+                    | Wide::Dload { index }
+                    | Wide::Aload { index } => {
+                        let value = self.local(*index as usize)?;
+                        self.push(value);
+                    }
+                    Wide::Istore { index }
+                    | Wide::Lstore { index }
+                    | Wide::Fstore { index }
+                    | Wide::Dstore { index }
+                    | Wide::Astore { index } => {
+                        let value = self.pop()?;
+                        self.set_local(*index as usize, value)?;
+                    }
+                    Wide::Iinc { index, value } => {
+                        let current = self.local(*index as usize)?;
+                        match current {
+                            Operand::Int(v) => self.set_local(
+                                *index as usize,
+                                Operand::Int(v.wrapping_add(*value as i32)),
+                            )?,
+                            other => {
+                                return Err(VmError::UnsupportedInstruction(format!(
+                                    "wide iinc on non-int local {other:?}"
+                                )))
+                            }
+                        }
+                    }
+                    Wide::Ret { .. } => {
+                        return Err(VmError::UnsupportedInstruction(
+                            "jsr/ret subroutines are not supported".to_string(),
+                        ))
+                    }
+                },
+
+                other => {
+                    return Err(VmError::UnsupportedInstruction(format!("{other:?}")));
+                }
+            }
+        }
+    }
+}
+
+/// Expands resolved argument values into JVM local-variable slots: a `Long`/`Double`
+/// value occupies two consecutive slots, the second an unusable placeholder, the same
+/// two-slot rule the constant pool applies via `CpInfo::Unusable`. Without this, every
+/// local after a wide argument would be indexed one slot too early.
+fn to_local_slots(operands: Vec<Operand>) -> Vec<Operand> {
+    let mut slots = Vec::with_capacity(operands.len());
+    for operand in operands {
+        let is_wide = matches!(operand, Operand::Long(_) | Operand::Double(_));
+        slots.push(operand);
+        if is_wide {
+            slots.push(Operand::Uninitialized);
+        }
+    }
+    slots
+}
+
+fn reference_eq(a: Operand, b: Operand) -> Result<bool, VmError> {
+    match (a, b) {
+        (Operand::Object(a), Operand::Object(b)) => Ok(a == b),
+        (a, b) => Err(VmError::UnsupportedInstruction(format!(
+            "if_acmp on non-reference operands {a:?}, {b:?}"
+        ))),
+    }
+}
+
+fn float_compare(a: f32, b: f32, nan_result: i32) -> i32 {
+    a.partial_cmp(&b).map(|o| o as i32).unwrap_or(nan_result)
+}
+
+fn double_compare(a: f64, b: f64, nan_result: i32) -> i32 {
+    a.partial_cmp(&b).map(|o| o as i32).unwrap_or(nan_result)
+}
+
@@ -0,0 +1,97 @@
+use cgr::classfile::ClassFile;
+
+fn read_fixture_bytes(name: &str) -> Vec<u8> {
+    std::fs::read(format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR")))
+        .expect("fixture class file should be readable")
+}
+
+/// Builds the smallest valid `.class` byte stream containing a single `Utf8`
+/// constant-pool entry holding `value`'s modified-UTF-8 encoding, so `pool.utf8(1)`
+/// can be exercised without needing a real compiled class.
+fn minimal_class_with_utf8(value: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend(0xCAFEBABEu32.to_be_bytes()); // magic
+    bytes.extend(0u16.to_be_bytes()); // minor_version
+    bytes.extend(61u16.to_be_bytes()); // major_version
+
+    bytes.extend(2u16.to_be_bytes()); // constant_pool_count (one entry, at index 1)
+    bytes.push(1); // tag: Utf8
+    let encoded = cesu8::to_java_cesu8(value);
+    bytes.extend((encoded.len() as u16).to_be_bytes());
+    bytes.extend(encoded.iter());
+
+    bytes.extend(0u16.to_be_bytes()); // access_flags
+    bytes.extend(1u16.to_be_bytes()); // this_class (unused by this test)
+    bytes.extend(0u16.to_be_bytes()); // super_class
+    bytes.extend(0u16.to_be_bytes()); // interfaces_count
+    bytes.extend(0u16.to_be_bytes()); // fields_count
+    bytes.extend(0u16.to_be_bytes()); // methods_count
+    bytes.extend(0u16.to_be_bytes()); // attributes_count
+
+    bytes
+}
+
+/// `ClassFile::write(ClassFile::read(bytes).1)` should reproduce `bytes` exactly:
+/// every constant-pool entry, access-flag set, and attribute is re-encoded from
+/// the parsed structure rather than copied from the input.
+#[test]
+fn round_trips_a_real_class_file_byte_for_byte() {
+    let bytes = read_fixture_bytes("Hello.class");
+    let (rest, class) = ClassFile::read(&bytes).expect("fixture class file should parse");
+    assert!(rest.is_empty());
+
+    assert_eq!(class.write(), bytes);
+}
+
+/// `Complex.class` declares `static long bigNum` and `static double d`, whose
+/// `<clinit>` initializers put a `Long` and a `Double` constant-pool entry in
+/// among the rest of the pool. Each occupies two consecutive indices, so a
+/// `FieldRef` sitting right after one only resolves correctly if the reader
+/// accounted for that gap rather than treating every entry as one slot.
+#[test]
+#[allow(clippy::approx_constant)] // 3.14159 is the literal from Complex.java, not an approximation of PI
+fn long_and_double_constants_keep_later_indices_aligned() {
+    let bytes = read_fixture_bytes("Complex.class");
+    let (rest, class) = ClassFile::read(&bytes).expect("fixture class file should parse");
+    assert!(rest.is_empty());
+
+    let pool = class.constant_pool();
+
+    assert_eq!(pool.long(38).unwrap(), 123456789012345);
+    assert_eq!(pool.field_ref(40).unwrap(), ("Complex", "bigNum", "J"));
+
+    assert_eq!(pool.double(44).unwrap(), 3.14159);
+    assert_eq!(pool.field_ref(46).unwrap(), ("Complex", "d", "D"));
+}
+
+/// Java's modified UTF-8 diverges from plain UTF-8 on exactly two shapes: NUL is
+/// the overlong two-byte `0xC0 0x80` rather than a literal zero byte, and
+/// supplementary characters are a six-byte CESU-8 surrogate pair rather than a
+/// four-byte UTF-8 sequence. A string exercising both should survive a
+/// write-then-read round trip through the constant pool unchanged.
+#[test]
+fn decodes_modified_utf8_nul_and_supplementary_characters() {
+    let value = "a\u{0}b\u{1F600}c";
+    let bytes = minimal_class_with_utf8(value);
+
+    let (rest, class) = ClassFile::read(&bytes).expect("synthetic class file should parse");
+    assert!(rest.is_empty());
+
+    assert_eq!(class.constant_pool().utf8(1).unwrap(), value);
+}
+
+/// The round-trip invariant from [`round_trips_a_real_class_file_byte_for_byte`]
+/// holds across a corpus of differently-shaped real `.class` files: a plain
+/// class, one with `long`/`double` constants and a `try`/`catch`, one with
+/// multiple fields and a loop, and one using `invokedynamic` (a lambda).
+#[test]
+fn round_trips_a_corpus_of_real_class_files_byte_for_byte() {
+    for name in ["Hello.class", "Complex.class", "Counter.class", "Lambda.class"] {
+        let bytes = read_fixture_bytes(name);
+        let (rest, class) =
+            ClassFile::read(&bytes).unwrap_or_else(|_| panic!("{name} should parse"));
+        assert!(rest.is_empty(), "{name} should be fully consumed");
+
+        assert_eq!(class.write(), bytes, "{name} should round-trip byte-for-byte");
+    }
+}
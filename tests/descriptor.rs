@@ -0,0 +1,54 @@
+use cgr::descriptor::{parse_field_type, parse_method_descriptor, FieldType, ReturnDescriptor};
+use cgr::error::ClassFileError;
+
+#[test]
+fn parses_a_valid_method_descriptor() {
+    let descriptor =
+        parse_method_descriptor("(ILjava/lang/String;[[D)Z").expect("should parse");
+
+    assert_eq!(
+        descriptor.parameters,
+        vec![
+            FieldType::Int,
+            FieldType::Object("java/lang/String".to_string()),
+            FieldType::Array {
+                dimensions: 2,
+                element: Box::new(FieldType::Double),
+            },
+        ]
+    );
+    assert_eq!(
+        descriptor.return_type,
+        ReturnDescriptor::Type(FieldType::Boolean)
+    );
+}
+
+#[test]
+fn rejects_an_unterminated_object_type() {
+    let err = parse_field_type("Ljava/lang/String").unwrap_err();
+    assert!(matches!(
+        err,
+        ClassFileError::InvalidDescriptor { reason: "unterminated object type", .. }
+    ));
+}
+
+#[test]
+fn rejects_trailing_garbage_after_the_return_type() {
+    let err = parse_method_descriptor("(I)Vx").unwrap_err();
+    assert!(matches!(
+        err,
+        ClassFileError::InvalidDescriptor {
+            reason: "trailing characters after return type",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn rejects_an_array_with_no_element_type() {
+    let err = parse_field_type("[").unwrap_err();
+    assert!(matches!(
+        err,
+        ClassFileError::InvalidDescriptor { reason: "array with no element type", .. }
+    ));
+}
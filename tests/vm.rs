@@ -0,0 +1,44 @@
+use cgr::classfile::ClassFile;
+use cgr::vm::{Operand, Vm};
+
+fn read_fixture(name: &str) -> ClassFile {
+    let bytes = std::fs::read(format!("{}/tests/fixtures/{name}", env!("CARGO_MANIFEST_DIR")))
+        .expect("fixture class file should be readable");
+    ClassFile::read(&bytes)
+        .expect("fixture class file should parse")
+        .1
+}
+
+/// `Counter.run()` allocates a `Counter`, adds 3 then 4 to its field via two
+/// `add` calls, and returns the result through `getValue` — exercising `new`,
+/// `invokespecial`/`invokevirtual`/`invokestatic`, `getfield`/`putfield`, and
+/// integer arithmetic in a single end-to-end interpreter run.
+#[test]
+fn runs_counter_run_to_completion() {
+    let class = read_fixture("Counter.class");
+    let mut vm = Vm::new(&class);
+
+    let result = vm
+        .call("run", "()I", Vec::new())
+        .expect("run() should execute without error");
+
+    assert!(matches!(result, Some(Operand::Int(7))));
+}
+
+/// `WideLocals.combine(long, int)` is invoked (via `invokestatic`) with a `long`
+/// parameter followed by an `int` parameter, compiled against real JVM local
+/// slots: `a` occupies slots 0-1 and `b` sits at slot 2 (`iload_2`). If the
+/// interpreter packed one `Operand` per parameter instead of widening `long`/
+/// `double` arguments to two slots, `b` would land at slot 1 and `combine`
+/// would read the wrong (or an uninitialized) value.
+#[test]
+fn runs_a_long_parameter_followed_by_another_parameter() {
+    let class = read_fixture("WideLocals.class");
+    let mut vm = Vm::new(&class);
+
+    let result = vm
+        .call("run", "()I", Vec::new())
+        .expect("run() should execute without error");
+
+    assert!(matches!(result, Some(Operand::Int(47))));
+}